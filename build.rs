@@ -0,0 +1,51 @@
+//! Generates a registry of all solutions in `src/bin` so the main binary can run them in-process.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    // solutions are named by year and day, e.g. `2023-01.rs`.
+    let mut puzzles: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .filter_map(|entry| {
+                    let path = entry.path();
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    let (year, day) = stem.split_once('-')?;
+                    let is_puzzle = path.extension()? == "rs"
+                        && year.parse::<u16>().is_ok_and(|y| y >= 2015)
+                        && day.len() == 2
+                        && day.parse::<u8>().is_ok_and(|d| (1..=25).contains(&d));
+                    is_puzzle.then(|| (format!("{year}_{day}"), path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    puzzles.sort();
+
+    let mut registry = String::new();
+
+    for (puzzle, path) in &puzzles {
+        registry.push_str(&format!(
+            "#[cfg(not(test))]\n#[allow(dead_code)]\n#[path = {path:?}]\nmod puzzle_{puzzle};\n"
+        ));
+    }
+
+    registry.push_str("\n#[cfg(not(test))]\npub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[\n");
+    for (puzzle, _) in &puzzles {
+        registry.push_str(&format!("    puzzle_{puzzle}::SOLUTION,\n"));
+    }
+    registry.push_str("];\n");
+
+    registry.push_str(
+        "\n#[cfg(test)]\npub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];\n",
+    );
+
+    let out_dir = env::var("OUT_DIR").unwrap();
+    fs::write(Path::new(&out_dir).join("solutions.rs"), registry).unwrap();
+}
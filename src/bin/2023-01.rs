@@ -1,6 +1,6 @@
 use regex::Regex;
 
-advent_of_code::solution!(1);
+advent_of_code::solution!(2023, 1);
 
 fn parse_pseudo_digit(pseudo_digit: &str) -> u32 {
     match pseudo_digit {
@@ -37,7 +37,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         total += parse_pseudo_digit(&last);
     });
 
-    return Some(total);
+    Some(total)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
@@ -63,7 +63,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         total += parse_pseudo_digit(&last);
     });
 
-    return Some(total);
+    Some(total)
 }
 
 #[cfg(test)]
@@ -73,7 +73,7 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
+            "examples", PUZZLE, 1,
         ));
         assert_eq!(result, Some(142));
     }
@@ -81,7 +81,7 @@ mod tests {
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
+            "examples", PUZZLE, 2,
         ));
         assert_eq!(result, Some(281));
     }
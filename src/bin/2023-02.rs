@@ -1,14 +1,22 @@
 use std::cmp;
 
+use advent_of_code::parse::InputError;
 use regex::Regex;
 
-advent_of_code::solution!(2);
+advent_of_code::solution!(2023, 2);
 
 const MAX_RED: u32 = 12;
 const MAX_GREEN: u32 = 13;
 const MAX_BLUE: u32 = 14;
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// Parses a number captured from `input`, pointing at it if it does not fit.
+fn parse_number(input: &str, number: &str) -> Result<u32, InputError> {
+    number
+        .parse()
+        .map_err(|e| InputError::at(input, number, format!("invalid number \"{number}\": {e}")))
+}
+
+pub fn part_one(input: &str) -> Result<u32, InputError> {
     let game_id_regex = Regex::new(r"Game (?<id>\d+)").unwrap();
     let color_regex = Regex::new(r"(?<amount>\d+) (?<color>red|green|blue)").unwrap();
 
@@ -17,7 +25,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         let mut above_max = false;
         for res in color_regex.captures_iter(line) {
             let color = &res["color"];
-            let amount = res["amount"].parse::<u32>().unwrap();
+            let amount = parse_number(input, &res["amount"])?;
 
             above_max = match color {
                 "red" => amount > MAX_RED,
@@ -31,10 +39,10 @@ pub fn part_one(input: &str) -> Option<u32> {
             }
         }
 
-        let Some(caps) = game_id_regex.captures(line) else {
-            return None;
-        };
-        let game_id = caps["id"].parse::<u32>().unwrap();
+        let caps = game_id_regex
+            .captures(line)
+            .ok_or_else(|| InputError::at(input, line, "expected \"Game <id>\""))?;
+        let game_id = parse_number(input, &caps["id"])?;
 
         if above_max {
             continue;
@@ -43,7 +51,7 @@ pub fn part_one(input: &str) -> Option<u32> {
         total += game_id;
     }
 
-    return Some(total);
+    Ok(total)
 }
 
 fn max_or_default(current_max: u32, new_value: u32) -> u32 {
@@ -54,7 +62,7 @@ fn max_or_default(current_max: u32, new_value: u32) -> u32 {
     }
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(input: &str) -> Result<u32, InputError> {
     let color_regex = Regex::new(r"(?<amount>\d+) (?<color>red|green|blue)").unwrap();
 
     let mut total: u32 = 0;
@@ -65,7 +73,7 @@ pub fn part_two(input: &str) -> Option<u32> {
 
         for res in color_regex.captures_iter(line) {
             let color = &res["color"];
-            let amount = res["amount"].parse::<u32>().unwrap();
+            let amount = parse_number(input, &res["amount"])?;
 
             match color {
                 "red" => min_red = max_or_default(min_red, amount),
@@ -79,7 +87,7 @@ pub fn part_two(input: &str) -> Option<u32> {
         total += power;
     }
 
-    return Some(total);
+    Ok(total)
 }
 
 #[cfg(test)]
@@ -88,13 +96,22 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(2286));
+    }
+
+    #[test]
+    fn test_malformed_input() {
+        let result = part_one("Game 1: 3 blue\nGame x: 4 red");
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "line 2, column 1: expected \"Game <id>\""
+        );
     }
 }
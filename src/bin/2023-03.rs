@@ -0,0 +1,93 @@
+use advent_of_code::grid::{Grid, Point};
+
+advent_of_code::solution!(2023, 3);
+
+/// A number in the schematic and the points its digits cover.
+struct Number {
+    value: u32,
+    points: Vec<Point>,
+}
+
+fn parse_numbers(grid: &Grid<char>) -> Vec<Number> {
+    let mut numbers = vec![];
+
+    for (y, row) in grid.rows().enumerate() {
+        let mut x = 0;
+        while x < row.len() {
+            let len = row[x..].iter().take_while(|c| c.is_ascii_digit()).count();
+            if len == 0 {
+                x += 1;
+                continue;
+            }
+
+            numbers.push(Number {
+                value: row[x..x + len].iter().collect::<String>().parse().unwrap(),
+                points: (x..x + len).map(|x| Point::new(x, y)).collect(),
+            });
+            x += len;
+        }
+    }
+
+    numbers
+}
+
+/// Whether any digit of `number` is adjacent (including diagonals) to a cell matching `predicate`.
+fn is_adjacent(grid: &Grid<char>, number: &Number, predicate: impl Fn(Point) -> bool) -> bool {
+    number
+        .points
+        .iter()
+        .any(|&p| grid.all_neighbours(p).any(&predicate))
+}
+
+fn is_symbol(c: char) -> bool {
+    !c.is_ascii_digit() && c != '.'
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().ok()?;
+
+    let sum = parse_numbers(&grid)
+        .iter()
+        .filter(|number| is_adjacent(&grid, number, |p| is_symbol(grid[p])))
+        .map(|number| number.value)
+        .sum();
+
+    Some(sum)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let grid: Grid<char> = input.parse().ok()?;
+    let numbers = parse_numbers(&grid);
+
+    let sum = grid
+        .positions(|&c| c == '*')
+        .filter_map(|gear| {
+            let adjacent: Vec<u32> = numbers
+                .iter()
+                .filter(|number| is_adjacent(&grid, number, |p| p == gear))
+                .map(|number| number.value)
+                .collect();
+
+            (adjacent.len() == 2).then(|| adjacent[0] * adjacent[1])
+        })
+        .sum();
+
+    Some(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(4361));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(467835));
+    }
+}
@@ -0,0 +1,82 @@
+use std::collections::HashSet;
+
+use advent_of_code::parse::{lines, record, uints, InputError};
+
+advent_of_code::solution!(2023, 4, parse?);
+
+pub struct Card {
+    winning: HashSet<u32>,
+    own: Vec<u32>,
+}
+
+impl Card {
+    fn matches(&self) -> usize {
+        self.own.iter().filter(|n| self.winning.contains(n)).count()
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Card>, InputError> {
+    lines(input, |line| {
+        let (_, numbers) = record(line)?;
+        let (winning, own) = numbers
+            .split_once('|')
+            .ok_or_else(|| InputError::at(line, numbers, "expected \"<winning> | <own>\""))?;
+
+        Ok(Card {
+            winning: uints(winning).collect::<Result<_, _>>()?,
+            own: uints(own).collect::<Result<_, _>>()?,
+        })
+    })
+}
+
+pub fn part_one(cards: &[Card]) -> Option<u32> {
+    let mut points = 0;
+    for card in cards {
+        let mut card_points = 0;
+        for number in &card.own {
+            if !card.winning.contains(number) {
+                continue;
+            }
+            card_points = match card_points {
+                0 => 1,
+                n => n * 2,
+            }
+        }
+
+        points += card_points;
+    }
+
+    Some(points)
+}
+
+pub fn part_two(cards: &[Card]) -> Option<u32> {
+    let mut cards_amount: Vec<u32> = cards.iter().map(|_| 1).collect();
+    for (card, scratchcard) in cards.iter().enumerate() {
+        let matches = scratchcard.matches();
+        // matches + 1 to be inclusive
+        for m in card + 1..card + matches + 1 {
+            cards_amount[m] += cards_amount[card];
+        }
+    }
+
+    Some(cards_amount.iter().sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let cards = parse(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_one(&cards.unwrap());
+        assert_eq!(result, Some(13));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let cards = parse(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_two(&cards.unwrap());
+        assert_eq!(result, Some(30));
+    }
+}
@@ -0,0 +1,101 @@
+use advent_of_code::interval::{Interval, IntervalMap};
+use advent_of_code::parse::{ints, record, tuple, Input, InputError};
+use itertools::Itertools;
+
+advent_of_code::solution!(2023, 5, parse?);
+
+/// The maps from seeds to locations, in the order they are applied.
+const MAPS: [&str; 7] = [
+    "seed-to-soil",
+    "soil-to-fertilizer",
+    "fertilizer-to-water",
+    "water-to-light",
+    "light-to-temperature",
+    "temperature-to-humidity",
+    "humidity-to-location",
+];
+
+pub struct Almanac {
+    seeds: Vec<i64>,
+    // seed-to-soil, ..., humidity-to-location composed into a single map
+    seed_to_location: IntervalMap,
+}
+
+pub fn parse(text: &str) -> Result<Almanac, InputError> {
+    let input = Input::new(text);
+
+    let seeds = input
+        .header()?
+        .parse(|header| ints(record(header)?.1).collect())?;
+
+    let mut seed_to_location = IntervalMap::new();
+    for name in MAPS {
+        let mut map = IntervalMap::new();
+        input.section(name)?.parse_lines::<(), ()>(|line| {
+            let (destination, source, size): (i64, i64, i64) = tuple(line)?;
+            map.try_insert(Interval::with_len(source, size), destination - source)
+                .map_err(|e| InputError::at(line, line, e.to_string()))
+        })?;
+        seed_to_location = seed_to_location.compose(&map);
+    }
+
+    Ok(Almanac {
+        seeds,
+        seed_to_location,
+    })
+}
+
+pub fn part_one(almanac: &Almanac) -> Option<i64> {
+    almanac
+        .seeds
+        .iter()
+        .map(|&seed| almanac.seed_to_location.get(seed))
+        .min()
+}
+
+pub fn part_two(almanac: &Almanac) -> Option<i64> {
+    let seed_ranges: Vec<Interval> = almanac
+        .seeds
+        .iter()
+        .tuples()
+        .map(|(&start, &size)| Interval::with_len(start, size))
+        .collect();
+
+    // push whole ranges through the map instead of every single seed.
+    almanac
+        .seed_to_location
+        .map_intervals(&seed_ranges)
+        .first()
+        .map(|locations| locations.start)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let almanac = parse(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_one(&almanac.unwrap());
+        assert_eq!(result, Some(35));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let almanac = parse(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_two(&almanac.unwrap());
+        assert_eq!(result, Some(46));
+    }
+
+    #[test]
+    fn test_overlapping_ranges() {
+        let input =
+            advent_of_code::template::read_file("examples", PUZZLE).replace("52 50 48", "52 50 49");
+        let error = parse(&input).err().unwrap();
+        assert_eq!(error.line, 5);
+        assert_eq!(
+            error.message,
+            "interval 50..99 overlaps the existing interval 98..100"
+        );
+    }
+}
@@ -1,6 +1,6 @@
-use regex::Regex;
+use advent_of_code::parse::{record, uints, InputError};
 
-advent_of_code::solution!(6);
+advent_of_code::solution!(2023, 6);
 
 fn quadratic_zero(a: f32, b: f32, c: f32) -> (f32, f32) {
     (
@@ -9,21 +9,22 @@ fn quadratic_zero(a: f32, b: f32, c: f32) -> (f32, f32) {
     )
 }
 
-// Paired with @asparagus
-pub fn part_one(input: &str) -> Option<u64> {
-    let number_re = Regex::new(r"\d+").unwrap();
-
-    let time_line = input.lines().nth(0).unwrap();
-    let times: Vec<u64> = number_re
-        .find_iter(time_line)
-        .map(|d| d.as_str().parse::<u64>().unwrap())
-        .collect();
+/// The numbers of the record on line `index`, e.g. `Time:      7  15   30`.
+fn numbers(input: &str, index: usize) -> Result<Vec<u64>, InputError> {
+    let line = input
+        .lines()
+        .nth(index)
+        .ok_or_else(|| InputError::new(index + 1, "expected a line of numbers"))?;
+    let (_, values) = record(line).map_err(|e| e.within(input))?;
+    uints(values)
+        .collect::<Result<_, _>>()
+        .map_err(|e| e.within(input))
+}
 
-    let distance_line = input.lines().nth(1).unwrap();
-    let distances: Vec<u64> = number_re
-        .find_iter(distance_line)
-        .map(|d| d.as_str().parse::<u64>().unwrap())
-        .collect();
+// Paired with @asparagus
+pub fn part_one(input: &str) -> Result<u64, InputError> {
+    let times = numbers(input, 0)?;
+    let distances = numbers(input, 1)?;
 
     let result = times
         .iter()
@@ -44,18 +45,17 @@ pub fn part_one(input: &str) -> Option<u64> {
                 res -= 1;
             }
 
-            return res;
+            res
         })
-        .fold(1, |acc, e| acc * e);
+        .product::<u64>();
 
-    return Some(result);
+    Ok(result)
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let whitespace_re = Regex::new(r"[^\S\r\n]").unwrap();
-    let clean_input = whitespace_re.replace_all(input, "");
+pub fn part_two(input: &str) -> Result<u64, InputError> {
+    let clean_input = input.replace([' ', '\t'], "");
 
-    return part_one(&clean_input);
+    part_one(&clean_input)
 }
 
 #[cfg(test)]
@@ -64,13 +64,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(288));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(71503));
     }
 }
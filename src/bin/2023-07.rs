@@ -2,11 +2,11 @@ use itertools::Itertools;
 use regex::Regex;
 use std::{cmp::Ordering, collections::BinaryHeap};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(2023, 7);
 
 fn get_hand_type(cards: &str) -> HandType {
     let mut counts = Vec::new();
-    for (_, group) in &cards.chars().sorted().group_by(|c| c.clone()) {
+    for (_, group) in &cards.chars().sorted().group_by(|c| *c) {
         counts.push(group.collect::<Vec<char>>().len());
     }
 
@@ -21,11 +21,11 @@ fn get_hand_type(cards: &str) -> HandType {
     }
 
     match (pairs, threes) {
-        (1, 1) => return HandType::FullHouse,
-        (0, 1) => return HandType::ThreeOfAKind,
-        (2, 0) => return HandType::TwoPair,
-        (1, 0) => return HandType::OnePair,
-        _ => return HandType::HighCard,
+        (1, 1) => HandType::FullHouse,
+        (0, 1) => HandType::ThreeOfAKind,
+        (2, 0) => HandType::TwoPair,
+        (1, 0) => HandType::OnePair,
+        _ => HandType::HighCard,
     }
 }
 
@@ -100,7 +100,7 @@ struct Hand {
 }
 impl PartialOrd for Hand {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(&other))
+        Some(self.cmp(other))
     }
 }
 impl Ord for Hand {
@@ -147,13 +147,13 @@ pub fn part_one(input: &str) -> Option<u64> {
     }
 
     let mut total = 0;
-    let mut multiplier = heap.iter().count() as u64;
+    let mut multiplier = heap.len() as u64;
     while let Some(hand) = heap.pop() {
         total += hand.bid * multiplier;
         multiplier -= 1;
     }
 
-    return Some(total);
+    Some(total)
 }
 
 /** Very similar to part one but we replace 'J' with '*', the changes in the logic are in the hand itself */
@@ -173,13 +173,13 @@ pub fn part_two(input: &str) -> Option<u64> {
     }
 
     let mut total = 0;
-    let mut multiplier = heap.iter().count() as u64;
+    let mut multiplier = heap.len() as u64;
     while let Some(hand) = heap.pop() {
         total += hand.bid * multiplier;
         multiplier -= 1;
     }
 
-    return Some(total);
+    Some(total)
 }
 
 #[cfg(test)]
@@ -188,13 +188,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6440));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(5905));
     }
 
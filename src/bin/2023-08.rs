@@ -0,0 +1,208 @@
+use advent_of_code::cycles::{brent, crt, Congruence, Cycle};
+use advent_of_code::parse::{tuple, Input, InputError};
+use itertools::Itertools;
+use std::{collections::HashMap, iter};
+
+advent_of_code::solution!(2023, 8, parse?);
+
+/// A node and the index of the next movement.
+type State = (usize, usize);
+
+pub struct Network {
+    /// Whether each movement goes to the left.
+    movements: Vec<bool>,
+    names: Vec<String>,
+    /// The left and right neighbour of every node.
+    nodes: Vec<(usize, usize)>,
+}
+
+impl Network {
+    fn node(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    fn step(&self, (node, movement): State) -> State {
+        let (left, right) = self.nodes[node];
+        let next = if self.movements[movement] {
+            left
+        } else {
+            right
+        };
+        (next, (movement + 1) % self.movements.len())
+    }
+
+    fn is_end(&self, (node, _): State) -> bool {
+        self.names[node].ends_with('Z')
+    }
+}
+
+pub fn parse(text: &str) -> Result<Network, InputError> {
+    let input = Input::new(text);
+
+    let movements = input.header()?.parse(|header| {
+        let header = header.trim_end();
+        match header.find(|c| c != 'L' && c != 'R') {
+            Some(i) => Err(InputError::at(
+                header,
+                &header[i..],
+                "expected \"L\" or \"R\"",
+            )),
+            None if header.is_empty() => Err(InputError::new(1, "expected movements")),
+            None => Ok(header.chars().map(|c| c == 'L').collect()),
+        }
+    })?;
+
+    let lines: Vec<(&str, &str, (String, String))> = input
+        .sections()
+        .next()
+        .ok_or_else(|| InputError::new(3, "expected the nodes"))?
+        .parse_lines(|line| {
+            let (id, targets) = line.split_once(" = ").ok_or_else(|| {
+                InputError::at(line, line, "expected \"<id> = (<left>, <right>)\"")
+            })?;
+            let pair = tuple(targets.trim_start_matches('(').trim_end_matches(')'))?;
+            Ok((id, targets, pair))
+        })?;
+
+    let ids: HashMap<&str, usize> = lines
+        .iter()
+        .enumerate()
+        .map(|(index, (id, _, _))| (*id, index))
+        .collect();
+    let nodes = lines
+        .iter()
+        .map(|(_, targets, (left, right))| {
+            let find = |name: &String| {
+                ids.get(name.as_str()).copied().ok_or_else(|| {
+                    InputError::at(text, targets, format!("unknown node \"{name}\""))
+                })
+            };
+            Ok((find(left)?, find(right)?))
+        })
+        .collect::<Result<_, InputError>>()?;
+
+    Ok(Network {
+        movements,
+        names: lines.iter().map(|(id, _, _)| id.to_string()).collect(),
+        nodes,
+    })
+}
+
+pub fn part_one(network: &Network) -> Option<u64> {
+    let end = network.node("ZZZ")?;
+    let mut state = (network.node("AAA")?, 0);
+    let mut count = 0;
+
+    while state.0 != end {
+        state = network.step(state);
+        count += 1;
+    }
+
+    Some(count)
+}
+
+/// The cycle of a ghost's walk and the steps up to the end of its first period at which it is on
+/// an end node.
+struct Ghost {
+    cycle: Cycle,
+    ends: Vec<usize>,
+}
+
+impl Ghost {
+    fn new(network: &Network, start: State) -> Self {
+        let cycle = brent(start, |&state| network.step(state));
+        let ends = iter::successors(Some(start), |&state| Some(network.step(state)))
+            .take(cycle.offset + cycle.period)
+            .positions(|state| network.is_end(state))
+            .collect();
+        Self { cycle, ends }
+    }
+}
+
+pub fn part_two(network: &Network) -> Option<u64> {
+    let starts: Vec<State> = (0..network.names.len())
+        .filter(|&node| network.names[node].ends_with('A'))
+        .map(|node| (node, 0))
+        .collect();
+    let ghosts: Vec<Ghost> = starts
+        .iter()
+        .map(|&start| Ghost::new(network, start))
+        .collect();
+
+    // Until every ghost has entered its cycle, the ghosts are walked together.
+    let transient = ghosts.iter().map(|ghost| ghost.cycle.offset).max()?;
+    let mut states = starts;
+    for step in 0..transient {
+        if states.iter().all(|&state| network.is_end(state)) {
+            return Some(step as u64);
+        }
+        for state in &mut states {
+            *state = network.step(*state);
+        }
+    }
+
+    // Afterwards, a ghost is on an end node at the steps congruent to one of the ends in its cycle.
+    ghosts
+        .iter()
+        .map(|ghost| {
+            let period = ghost.cycle.period as i64;
+            ghost
+                .ends
+                .iter()
+                .filter(|&&end| end >= ghost.cycle.offset)
+                .map(move |&end| Congruence::new(end as i64, period))
+        })
+        .multi_cartesian_product()
+        .filter_map(crt)
+        .map(|steps| steps.first_at_least(transient as i64))
+        .min()
+        .map(|steps| steps as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let network = parse(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 1,
+        ));
+        let result = part_one(&network.unwrap());
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let network = parse(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 2,
+        ));
+        let result = part_two(&network.unwrap());
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn test_part_two_with_offsets() {
+        // 11A reaches its end after 1 step and then every 2 steps, 22A after 2 steps and then every
+        // 3 steps: the LCM of the first ends would be 2, but both are on an end after 5 steps.
+        let network = parse(
+            "L
+
+11A = (11Z, 11A)
+11Z = (11B, 11B)
+11B = (11Z, 11Z)
+22A = (22B, 22A)
+22B = (22Z, 22Z)
+22Z = (22C, 22C)
+22C = (22D, 22D)
+22D = (22Z, 22Z)",
+        );
+        assert_eq!(part_two(&network.unwrap()), Some(5));
+    }
+
+    #[test]
+    fn test_unknown_node() {
+        let error = parse("L\n\nAAA = (AAA, BBB)").err().unwrap();
+        assert_eq!((error.line, error.column), (3, Some(7)));
+    }
+}
@@ -0,0 +1,65 @@
+use advent_of_code::parse::{ints, lines, InputError};
+use itertools::Itertools;
+
+advent_of_code::solution!(2023, 9, parse?);
+
+fn find_step_recursive(nums: &[i64]) -> i64 {
+    let diffs: Vec<i64> = nums.iter().tuple_windows().map(|(a, b)| b - a).collect();
+    if diffs.iter().all_equal() {
+        *diffs.last().unwrap()
+    } else {
+        *diffs.last().unwrap() + find_step_recursive(&diffs)
+    }
+}
+
+pub fn parse(input: &str) -> Result<Vec<Vec<i64>>, InputError> {
+    lines(input, |history| ints(history).collect())
+}
+
+pub fn part_one(histories: &[Vec<i64>]) -> Option<i64> {
+    let sum = histories
+        .iter()
+        .map(|nums| {
+            let last = nums.last().unwrap();
+
+            last + find_step_recursive(nums)
+        })
+        .sum();
+
+    Some(sum)
+}
+
+pub fn part_two(histories: &[Vec<i64>]) -> Option<i64> {
+    let sum = histories
+        .iter()
+        .map(|history| {
+            let mut nums = history.clone();
+            nums.reverse();
+
+            let last = nums.last().unwrap();
+
+            last + find_step_recursive(&nums)
+        })
+        .sum();
+
+    Some(sum)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let histories = parse(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_one(&histories.unwrap());
+        assert_eq!(result, Some(114));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let histories = parse(&advent_of_code::template::read_file("examples", PUZZLE));
+        let result = part_two(&histories.unwrap());
+        assert_eq!(result, Some(2));
+    }
+}
@@ -0,0 +1,183 @@
+use std::collections::HashSet;
+
+use advent_of_code::grid::{Direction, Grid, GridError, Point};
+use advent_of_code::parse::InputError;
+
+advent_of_code::solution!(2023, 10, parse?);
+
+const NORTH: Direction = Direction::NORTH;
+const SOUTH: Direction = Direction::SOUTH;
+const WEST: Direction = Direction::WEST;
+const EAST: Direction = Direction::EAST;
+
+fn can_receive(direction: Direction, symbol: char) -> bool {
+    match direction {
+        WEST => ['S', 'L', 'F', '-'].contains(&symbol),
+        EAST => ['S', '7', 'J', '-'].contains(&symbol),
+        SOUTH => ['S', 'L', 'J', '|'].contains(&symbol),
+        NORTH => ['S', 'F', '7', '|'].contains(&symbol),
+        _ => false,
+    }
+}
+
+fn are_connected(grid: &Grid<char>, direction: Direction, f_pos: Point, t_pos: Point) -> bool {
+    let from = grid[f_pos];
+    let to = grid[t_pos];
+
+    match from {
+        '|' => match direction {
+            NORTH | SOUTH => can_receive(direction, to),
+            _ => false,
+        },
+        '-' => match direction {
+            WEST | EAST => can_receive(direction, to),
+            _ => false,
+        },
+        'L' => match direction {
+            NORTH | EAST => can_receive(direction, to),
+            _ => false,
+        },
+        'J' => match direction {
+            NORTH | WEST => can_receive(direction, to),
+            _ => false,
+        },
+        '7' => match direction {
+            SOUTH | WEST => can_receive(direction, to),
+            _ => false,
+        },
+        'F' => match direction {
+            SOUTH | EAST => can_receive(direction, to),
+            _ => false,
+        },
+        '.' => false,
+        'S' => can_receive(direction, to),
+        _ => false,
+    }
+}
+
+fn move_tile(
+    grid: &Grid<char>,
+    visited: &mut HashSet<Point>,
+    tile: Point,
+    prev_dir: Option<Direction>,
+) -> Option<(Point, Direction)> {
+    for m in [NORTH, SOUTH, EAST, WEST] {
+        // avoid going back
+        if Some(m.opposite()) == prev_dir {
+            continue;
+        }
+
+        let Some(next) = grid.step(tile, m) else {
+            continue;
+        };
+
+        if !visited.contains(&next) && are_connected(grid, m, tile, next) {
+            visited.insert(next);
+            return Some((next, m));
+        }
+    }
+
+    None
+}
+
+/// An error pointing at `tile`.
+fn tile_error(tile: Point, message: &str) -> InputError {
+    InputError::new(tile.y + 1, message).with_column(tile.x + 1)
+}
+
+/// Walks the loop that starts at `S`. Returns the tiles of the loop in walking order, starting with `S`.
+pub fn parse(input: &str) -> Result<Vec<Point>, InputError> {
+    let grid: Grid<char> = input.parse().map_err(|e: GridError| match e {
+        GridError::Ragged { row, .. } => InputError::new(row + 1, e.to_string()),
+        GridError::Empty => InputError::new(1, e.to_string()),
+    })?;
+    let start = grid
+        .find(|&c| c == 'S')
+        .ok_or_else(|| InputError::new(1, "expected a start tile \"S\""))?;
+
+    let mut visited: HashSet<Point> = HashSet::new();
+    let mut path = vec![start];
+
+    let (mut current, mut prev_dir) = move_tile(&grid, &mut visited, start, None)
+        .ok_or_else(|| tile_error(start, "the start tile is not connected to a pipe"))?;
+
+    while current != start {
+        path.push(current);
+        (current, prev_dir) = move_tile(&grid, &mut visited, current, Some(prev_dir))
+            .ok_or_else(|| tile_error(current, "the loop breaks off at this pipe"))?;
+    }
+
+    Ok(path)
+}
+
+pub fn part_one(path: &[Point]) -> Option<usize> {
+    Some(path.len() / 2)
+}
+
+pub fn part_two(path: &[Point]) -> Option<usize> {
+    // shoelace formula for the area enclosed by the loop.
+    let double_area = path
+        .iter()
+        .zip(path.iter().cycle().skip(1))
+        .map(|(a, b)| (a.x * b.y) as isize - (b.x * a.y) as isize)
+        .sum::<isize>()
+        .unsigned_abs();
+
+    // Pick's theorem: area = interior + boundary / 2 - 1.
+    Some((double_area + 2 - path.len()) / 2)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reads one of the additional examples of part two, e.g. `10-2-squeezed.txt`.
+    fn read_variant(name: &str) -> String {
+        let path = PUZZLE
+            .example_path(Some(2))
+            .replace("-2.txt", &format!("-2-{name}.txt"));
+        std::fs::read_to_string(path).expect("could not open example file")
+    }
+
+    #[test]
+    fn test_part_one() {
+        let path = parse(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 1,
+        ));
+        let result = part_one(&path.unwrap());
+        assert_eq!(result, Some(4));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let path = parse(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 2,
+        ));
+        let result = part_two(&path.unwrap());
+        assert_eq!(result, Some(4));
+    }
+
+    #[test]
+    fn test_part_two_squeezed() {
+        let result = part_two(&parse(&read_variant("squeezed")).unwrap());
+        assert_eq!(result, Some(4));
+    }
+
+    #[test]
+    fn test_part_two_larger() {
+        let result = part_two(&parse(&read_variant("larger")).unwrap());
+        assert_eq!(result, Some(8));
+    }
+
+    #[test]
+    fn test_invalid_loops() {
+        let error = parse(".....\n.F-7.\n.|.|.\n.L-J.\n.....").unwrap_err();
+        assert_eq!(error.to_string(), "line 1: expected a start tile \"S\"");
+
+        let error = parse(".....\n.S-7.\n.|...\n.L-J.\n.....").unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "line 4, column 4: the loop breaks off at this pipe"
+        );
+    }
+}
@@ -0,0 +1,246 @@
+use num::{Integer, Signed};
+
+/// Where the sequence `start, next(start), next(next(start)), ...` starts repeating.
+///
+/// After `offset` steps, the sequence repeats every `period` steps: the states at steps `offset + i`
+/// and `offset + i + period` are equal for every `i`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Cycle {
+    pub offset: usize,
+    pub period: usize,
+}
+
+impl Cycle {
+    /// The first step with the same state as `step`, e.g. to find the state after a billion steps.
+    pub fn equivalent(&self, step: usize) -> usize {
+        if step < self.offset {
+            step
+        } else {
+            self.offset + (step - self.offset) % self.period
+        }
+    }
+}
+
+/// Finds the cycle of the states reached from `start` with Brent's algorithm.
+///
+/// Only two states are kept at a time and `next` is called about `2 * (offset + period)` times.
+/// Never returns if the sequence does not repeat.
+///
+/// ```
+/// # use advent_of_code::cycles::{brent, Cycle};
+/// let cycle = brent(0, |x| if *x < 5 { x + 1 } else { 2 });
+/// assert_eq!(cycle, Cycle { offset: 2, period: 4 });
+/// ```
+pub fn brent<S: Clone + PartialEq>(start: S, mut next: impl FnMut(&S) -> S) -> Cycle {
+    // The tortoise waits at the start of windows of doubling length until the hare runs into it.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = next(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = next(&hare);
+        period += 1;
+    }
+
+    Cycle {
+        offset: offset(start, period, next),
+        period,
+    }
+}
+
+/// Finds the cycle of the states reached from `start` with Floyd's algorithm.
+///
+/// Like [`brent`], but calls `next` about `3 * (offset + period)` times.
+pub fn floyd<S: Clone + PartialEq>(start: S, mut next: impl FnMut(&S) -> S) -> Cycle {
+    // The hare runs twice as fast as the tortoise, they meet at a multiple of the period.
+    let mut tortoise = next(&start);
+    let mut hare = next(&tortoise);
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        let half = next(&hare);
+        hare = next(&half);
+    }
+
+    let mut period = 1;
+    let mut hare = next(&tortoise);
+    while tortoise != hare {
+        hare = next(&hare);
+        period += 1;
+    }
+
+    Cycle {
+        offset: offset(start, period, next),
+        period,
+    }
+}
+
+/// The steps before the cycle of length `period` starts.
+fn offset<S: Clone + PartialEq>(start: S, period: usize, mut next: impl FnMut(&S) -> S) -> usize {
+    let mut tortoise = start.clone();
+    let mut hare = start;
+    for _ in 0..period {
+        hare = next(&hare);
+    }
+
+    let mut offset = 0;
+    while tortoise != hare {
+        tortoise = next(&tortoise);
+        hare = next(&hare);
+        offset += 1;
+    }
+    offset
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The integers `x` with `x ≡ residue (mod modulus)`. The residue is kept in `0..modulus`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Congruence<T> {
+    pub residue: T,
+    pub modulus: T,
+}
+
+impl<T: Integer + Signed + Clone> Congruence<T> {
+    /// # Panics
+    /// Panics if `modulus` is not positive.
+    pub fn new(residue: T, modulus: T) -> Self {
+        assert!(modulus.is_positive(), "the modulus must be positive");
+        Self {
+            residue: residue.mod_floor(&modulus),
+            modulus,
+        }
+    }
+
+    /// The integers that satisfy both congruences, [`None`] if there are none.
+    /// The moduli do not have to be coprime.
+    pub fn and(&self, other: &Self) -> Option<Self> {
+        let gcd = self.modulus.extended_gcd(&other.modulus);
+        let difference = other.residue.clone() - self.residue.clone();
+        if !difference.is_multiple_of(&gcd.gcd) {
+            return None;
+        }
+
+        // `x = self.residue + self.modulus * k` where `k` solves
+        // `self.modulus / gcd * k ≡ difference / gcd (mod other.modulus / gcd)`.
+        let step = other.modulus.clone() / gcd.gcd.clone();
+        let k = (difference / gcd.gcd * gcd.x).mod_floor(&step);
+        let modulus = self.modulus.clone() * step;
+        Some(Self::new(
+            self.residue.clone() + self.modulus.clone() * k,
+            modulus,
+        ))
+    }
+
+    /// The smallest integer of the congruence that is not below `min`.
+    pub fn first_at_least(&self, min: T) -> T {
+        min.clone() + (self.residue.clone() - min).mod_floor(&self.modulus)
+    }
+}
+
+/// Solves a system of congruences with the generalized Chinese remainder theorem.
+///
+/// Returns the integers that satisfy all of them as a single congruence modulo the lcm of the moduli,
+/// [`None`] if they contradict each other.
+///
+/// ```
+/// # use advent_of_code::cycles::{crt, Congruence};
+/// let x = crt([Congruence::new(2, 6), Congruence::new(4, 8)]).unwrap();
+/// assert_eq!(x, Congruence::new(20, 24));
+/// assert_eq!(x.first_at_least(100), 116);
+/// assert_eq!(crt([Congruence::new(1, 6), Congruence::new(2, 4)]), None);
+/// ```
+pub fn crt<T: Integer + Signed + Clone>(
+    congruences: impl IntoIterator<Item = Congruence<T>>,
+) -> Option<Congruence<T>> {
+    congruences
+        .into_iter()
+        .try_fold(Congruence::new(T::zero(), T::one()), |acc, c| acc.and(&c))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, crt, floyd, Congruence, Cycle};
+
+    /// `offset` states leading into a loop of `period` states.
+    fn rho(offset: usize, period: usize) -> impl Fn(&usize) -> usize {
+        move |&x| {
+            if x + 1 < offset + period {
+                x + 1
+            } else {
+                offset
+            }
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        for offset in 0..20 {
+            for period in 1..20 {
+                let expected = Cycle { offset, period };
+                assert_eq!(brent(0, rho(offset, period)), expected);
+                assert_eq!(floyd(0, rho(offset, period)), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn finds_equivalent_steps() {
+        let cycle = Cycle {
+            offset: 3,
+            period: 4,
+        };
+        assert_eq!(cycle.equivalent(2), 2);
+        assert_eq!(cycle.equivalent(3), 3);
+        assert_eq!(cycle.equivalent(7), 3);
+        assert_eq!(cycle.equivalent(1_000_000_002), 6);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt::<i64>([]), Some(Congruence::new(0, 1)));
+        assert_eq!(
+            crt([
+                Congruence::new(2, 3),
+                Congruence::new(3, 5),
+                Congruence::new(2, 7)
+            ]),
+            Some(Congruence::new(23, 105))
+        );
+        assert_eq!(
+            crt([Congruence::new(-1, 4), Congruence::new(9, 6)]),
+            Some(Congruence::new(3, 12))
+        );
+        assert_eq!(crt([Congruence::new(0, 4), Congruence::new(1, 6)]), None);
+    }
+
+    #[test]
+    fn brute_forces_congruences() {
+        for (m1, m2) in [(4, 6), (5, 7), (12, 18), (9, 9)] {
+            for r1 in 0..m1 {
+                for r2 in 0..m2 {
+                    let expected = (0..m1 * m2).find(|x| x % m1 == r1 && x % m2 == r2);
+                    let solved = crt([Congruence::new(r1, m1), Congruence::new(r2, m2)]);
+                    assert_eq!(solved.map(|c| c.residue), expected, "{r1} {m1} {r2} {m2}");
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn finds_first_value_at_least() {
+        let c = Congruence::new(3, 10);
+        assert_eq!(c.first_at_least(0), 3);
+        assert_eq!(c.first_at_least(3), 3);
+        assert_eq!(c.first_at_least(4), 13);
+        assert_eq!(c.first_at_least(-8), -7);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
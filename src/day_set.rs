@@ -0,0 +1,194 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use crate::{all_days, Day};
+
+/// A set of days of advent.
+///
+/// # Display
+/// This value displays as a comma separated list of days and ranges of days, which is also the format it parses from.
+///
+/// ```
+/// # use advent_of_code::{day, DaySet};
+/// let days: DaySet = "1-3,8".parse().unwrap();
+/// assert!(days.contains(day!(2)) && !days.contains(day!(4)));
+/// assert_eq!(days.to_string(), "1-3,8")
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct DaySet(u32);
+
+impl DaySet {
+    /// Creates an empty set.
+    pub const fn new() -> Self {
+        Self(0)
+    }
+
+    /// Creates the set of all days from the 1st to the 25th.
+    pub fn all() -> Self {
+        all_days().collect()
+    }
+
+    /// Creates the set of all days from `first` to `last`, both inclusive.
+    pub fn range(first: Day, last: Day) -> Self {
+        all_days()
+            .filter(|day| (first..=last).contains(day))
+            .collect()
+    }
+
+    pub fn insert(&mut self, day: Day) {
+        self.0 |= 1 << day.into_inner();
+    }
+
+    pub fn remove(&mut self, day: Day) {
+        self.0 &= !(1 << day.into_inner());
+    }
+
+    pub fn contains(&self, day: Day) -> bool {
+        self.0 & (1 << day.into_inner()) != 0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.count_ones() as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// The days that are in `self`, in `other` or in both.
+    #[must_use]
+    pub fn union(&self, other: &Self) -> Self {
+        Self(self.0 | other.0)
+    }
+
+    /// The days that are in `self` but not in `other`.
+    #[must_use]
+    pub fn difference(&self, other: &Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+
+    /// The days of the set on or after `day`.
+    #[must_use]
+    pub fn since(&self, day: Day) -> Self {
+        self.difference(&Self(u32::MAX >> (32 - day.into_inner())))
+    }
+
+    /// Iterates over the days of the set in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = Day> + '_ {
+        all_days().filter(|&day| self.contains(day))
+    }
+}
+
+impl FromIterator<Day> for DaySet {
+    fn from_iter<T: IntoIterator<Item = Day>>(iter: T) -> Self {
+        let mut set = Self::new();
+        for day in iter {
+            set.insert(day);
+        }
+        set
+    }
+}
+
+impl Display for DaySet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut ranges: Vec<(Day, Day)> = vec![];
+        for day in self.iter() {
+            match ranges.last_mut() {
+                Some((_, last)) if last.into_inner() + 1 == day.into_inner() => *last = day,
+                _ => ranges.push((day, day)),
+            }
+        }
+
+        let ranges: Vec<String> = ranges
+            .into_iter()
+            .map(|(first, last)| {
+                if first == last {
+                    first.into_inner().to_string()
+                } else {
+                    format!("{}-{}", first.into_inner(), last.into_inner())
+                }
+            })
+            .collect();
+
+        f.write_str(&ranges.join(","))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for DaySet {
+    type Err = DaySetFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut set = Self::new();
+
+        for item in s.split(',') {
+            let error = || DaySetFromStrError(item.trim().to_string());
+            let (first, last) = item.split_once('-').unwrap_or((item, item));
+            let first: Day = first.trim().parse().map_err(|_| error())?;
+            let last: Day = last.trim().parse().map_err(|_| error())?;
+
+            if first > last {
+                return Err(error());
+            }
+
+            set = set.union(&Self::range(first, last));
+        }
+
+        Ok(set)
+    }
+}
+
+/// An error which can be returned when parsing a [`DaySet`], contains the invalid item.
+#[derive(Debug)]
+pub struct DaySetFromStrError(String);
+
+impl Error for DaySetFromStrError {}
+
+impl Display for DaySetFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid days \"{}\", expecting days between 1 and 25 or ranges like 1-5, separated by commas",
+            self.0
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::DaySet;
+    use crate::day;
+
+    #[test]
+    fn parses_day_sets() {
+        let days: DaySet = "1-5, 8,10-10".parse().unwrap();
+        assert_eq!(days.len(), 7);
+        assert!(days.contains(day!(5)) && days.contains(day!(10)));
+        assert!(!days.contains(day!(6)) && !days.contains(day!(25)));
+        assert_eq!(days.to_string(), "1-5,8,10");
+
+        assert_eq!("1-25".parse::<DaySet>().unwrap(), DaySet::all());
+        assert!("".parse::<DaySet>().is_err());
+        assert!("5-1".parse::<DaySet>().is_err());
+        assert!("0-3".parse::<DaySet>().is_err());
+        assert!("1-26".parse::<DaySet>().is_err());
+        assert!("1-".parse::<DaySet>().is_err());
+    }
+
+    #[test]
+    fn filters_day_sets() {
+        let days = DaySet::all()
+            .difference(&"5,7".parse().unwrap())
+            .since(day!(4));
+        assert_eq!(days.to_string(), "4,6,8-25");
+        assert_eq!(days.iter().next(), Some(day!(4)));
+        assert_eq!(DaySet::all().since(day!(25)).len(), 1);
+        assert!(DaySet::new().is_empty());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
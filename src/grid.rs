@@ -0,0 +1,433 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// A position on a [`Grid`], `x` is the column and `y` the row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+}
+
+impl Point {
+    pub const fn new(x: usize, y: usize) -> Self {
+        Self { x, y }
+    }
+
+    /// Moves the point one step in `direction`, returns [`None`] if a coordinate would become negative.
+    /// Use [`Grid::step`] to also check the upper bounds of a grid.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        Some(Self {
+            x: self.x.checked_add_signed(direction.dx)?,
+            y: self.y.checked_add_signed(direction.dy)?,
+        })
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An offset between two neighbouring points. North points to the first row.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Direction {
+    pub dx: isize,
+    pub dy: isize,
+}
+
+impl Direction {
+    pub const NORTH: Self = Self::new(0, -1);
+    pub const SOUTH: Self = Self::new(0, 1);
+    pub const WEST: Self = Self::new(-1, 0);
+    pub const EAST: Self = Self::new(1, 0);
+    pub const NORTH_EAST: Self = Self::new(1, -1);
+    pub const NORTH_WEST: Self = Self::new(-1, -1);
+    pub const SOUTH_EAST: Self = Self::new(1, 1);
+    pub const SOUTH_WEST: Self = Self::new(-1, 1);
+
+    /// The four directions without diagonals, clockwise starting north.
+    pub const CARDINAL: [Self; 4] = [Self::NORTH, Self::EAST, Self::SOUTH, Self::WEST];
+
+    /// The eight directions including diagonals, clockwise starting north.
+    pub const ALL: [Self; 8] = [
+        Self::NORTH,
+        Self::NORTH_EAST,
+        Self::EAST,
+        Self::SOUTH_EAST,
+        Self::SOUTH,
+        Self::SOUTH_WEST,
+        Self::WEST,
+        Self::NORTH_WEST,
+    ];
+
+    pub const fn new(dx: isize, dy: isize) -> Self {
+        Self { dx, dy }
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        Self::new(-self.dx, -self.dy)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A rectangular grid of cells, stored row by row.
+///
+/// ```
+/// # use advent_of_code::grid::{Grid, Point};
+/// let grid: Grid<char> = "ab\ncd".parse().unwrap();
+/// assert_eq!(grid[Point::new(1, 0)], 'b');
+/// assert_eq!(grid.to_string(), "ab\ncd");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from `cells` in row order. Returns [`None`] if the cells do not fill whole rows.
+    pub fn from_cells(width: usize, cells: Vec<T>) -> Option<Self> {
+        if width == 0 || !cells.len().is_multiple_of(width) {
+            return None;
+        }
+
+        Some(Self {
+            width,
+            height: cells.len() / width,
+            cells,
+        })
+    }
+
+    /// Parses every line of `input` as a row, converting each character with `f`.
+    pub fn parse_with(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut cells = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let len = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let found = cells.len() - len;
+
+            match width {
+                None => width = Some(found),
+                Some(expected) if expected != found => {
+                    return Err(GridError::Ragged {
+                        row: y,
+                        expected,
+                        found,
+                    });
+                }
+                Some(_) => {}
+            }
+        }
+
+        width
+            .and_then(|width| Self::from_cells(width, cells))
+            .ok_or(GridError::Empty)
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Checks whether `point` lies within the grid.
+    pub fn contains(&self, point: Point) -> bool {
+        point.x < self.width && point.y < self.height
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point)
+            .then(|| &self.cells[point.y * self.width + point.x])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        self.contains(point)
+            .then(|| &mut self.cells[point.y * self.width + point.x])
+    }
+
+    /// Moves `point` one step in `direction`, returns [`None`] if the step leaves the grid.
+    pub fn step(&self, point: Point, direction: Direction) -> Option<Point> {
+        point.step(direction).filter(|&p| self.contains(p))
+    }
+
+    /// The up to four neighbours of `point` without diagonals.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |d| self.step(point, d))
+    }
+
+    /// The up to eight neighbours of `point` including diagonals.
+    pub fn all_neighbours(&self, point: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| self.step(point, d))
+    }
+
+    /// All points of the grid in row order.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::new(i % width, i / width))
+    }
+
+    /// All cells of the grid with their points in row order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    /// The cells of row `y`, or [`None`] if it is out of bounds.
+    pub fn row(&self, y: usize) -> Option<&[T]> {
+        (y < self.height).then(|| &self.cells[y * self.width..(y + 1) * self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // an empty grid has no cells, so any chunk size yields no rows.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// The cells of column `x` from top to bottom. Empty if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        let skip = if x < self.width { x } else { self.cells.len() };
+        self.cells.iter().skip(skip).step_by(self.width.max(1))
+    }
+
+    /// The points from `start` (inclusive) to the edge of the grid in `direction`.
+    /// E.g. [`Direction::SOUTH_EAST`] walks the diagonal starting at `start`.
+    pub fn ray(&self, start: Point, direction: Direction) -> impl Iterator<Item = Point> + '_ {
+        std::iter::successors(Some(start).filter(|&p| self.contains(p)), move |&p| {
+            self.step(p, direction)
+        })
+    }
+
+    /// The first point in row order whose cell matches `predicate`.
+    pub fn find(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// All points in row order whose cell matches `predicate`.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Point> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| predicate(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// Creates a grid of the same size with every cell converted by `f`.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T: Clone> Grid<T> {
+    /// Creates a grid of `width` x `height` cells with the value `fill`.
+    /// If either dimension is `0`, the grid is empty and both are `0`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        let (width, height) = if width == 0 || height == 0 {
+            (0, 0)
+        } else {
+            (width, height)
+        };
+
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point) -> &Self::Output {
+        self.get(point)
+            .unwrap_or_else(|| panic!("point {point} is outside of the grid"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, point: Point) -> &mut Self::Output {
+        self.get_mut(point)
+            .unwrap_or_else(|| panic!("point {point} is outside of the grid"))
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| c)
+    }
+}
+
+/// Displays every row on a separate line.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum GridError {
+    /// The input has no cells.
+    Empty,
+    /// A row has a different length than the first row.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridError::Empty => f.write_str("expecting at least one cell"),
+            GridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(f, "expecting {expected} cells in row {row}, found {found}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Direction, Grid, GridError, Point};
+
+    fn grid() -> Grid<char> {
+        "abc\ndef\nghi\njkl".parse().unwrap()
+    }
+
+    fn cells(grid: &Grid<char>, points: impl Iterator<Item = Point>) -> String {
+        points.map(|p| grid[p]).collect()
+    }
+
+    #[test]
+    fn creates_empty_grids() {
+        for (width, height) in [(0, 3), (3, 0), (0, 0)] {
+            let grid = Grid::new(width, height, '.');
+            assert_eq!((grid.width(), grid.height()), (0, 0));
+            assert_eq!(grid.rows().count(), 0);
+            assert_eq!(grid.column(0).count(), 0);
+            assert_eq!(grid.points().count(), 0);
+            assert_eq!(grid.to_string(), "");
+        }
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid[Point::new(2, 1)], 'f');
+        assert_eq!(grid.to_string(), "abc\ndef\nghi\njkl");
+
+        let digits = Grid::parse_with("12\n34", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.to_string(), "12\n34");
+    }
+
+    #[test]
+    fn errors_on_invalid_grids() {
+        assert_eq!("".parse::<Grid<char>>(), Err(GridError::Empty));
+        assert_eq!(
+            "ab\nc".parse::<Grid<char>>(),
+            Err(GridError::Ragged {
+                row: 1,
+                expected: 2,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn checks_bounds() {
+        let grid = grid();
+        assert_eq!(grid.get(Point::new(3, 0)), None);
+        assert_eq!(grid.get(Point::new(0, 4)), None);
+        assert_eq!(grid.step(Point::new(0, 0), Direction::NORTH), None);
+        assert_eq!(grid.step(Point::new(2, 3), Direction::EAST), None);
+        assert_eq!(
+            grid.step(Point::new(0, 0), Direction::SOUTH_EAST),
+            Some(Point::new(1, 1))
+        );
+    }
+
+    #[test]
+    fn iterates_neighbours() {
+        let grid = grid();
+        assert_eq!(cells(&grid, grid.neighbours(Point::new(1, 1))), "bfhd");
+        assert_eq!(cells(&grid, grid.neighbours(Point::new(0, 0))), "bd");
+        assert_eq!(
+            cells(&grid, grid.all_neighbours(Point::new(1, 1))),
+            "bcfihgda"
+        );
+        assert_eq!(cells(&grid, grid.all_neighbours(Point::new(0, 0))), "bed");
+    }
+
+    #[test]
+    fn views() {
+        let grid = grid();
+        assert_eq!(grid.row(1), Some(&['d', 'e', 'f'][..]));
+        assert_eq!(grid.row(4), None);
+        assert_eq!(grid.column(2).collect::<String>(), "cfil");
+        assert_eq!(grid.column(3).count(), 0);
+        assert_eq!(
+            cells(&grid, grid.ray(Point::new(0, 0), Direction::SOUTH_EAST)),
+            "aei"
+        );
+        assert_eq!(
+            cells(&grid, grid.ray(Point::new(0, 3), Direction::NORTH_EAST)),
+            "jhf"
+        );
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid: Grid<char> = "#.\n.#".parse().unwrap();
+        assert_eq!(grid.find(|&c| c == '.'), Some(Point::new(1, 0)));
+        assert_eq!(grid.find(|&c| c == 'x'), None);
+        assert_eq!(
+            grid.positions(|&c| c == '#').collect::<Vec<_>>(),
+            vec![Point::new(0, 0), Point::new(1, 1)]
+        );
+    }
+}
+
+/* -------------------------------------------------------------------------- */
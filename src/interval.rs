@@ -0,0 +1,354 @@
+use std::{error::Error, fmt::Display};
+
+/// A half-open range of integers `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Interval {
+    pub start: i64,
+    pub end: i64,
+}
+
+impl Interval {
+    pub const fn new(start: i64, end: i64) -> Self {
+        Self { start, end }
+    }
+
+    /// Creates the interval of `len` values beginning at `start`.
+    pub const fn with_len(start: i64, len: i64) -> Self {
+        Self::new(start, start + len)
+    }
+
+    pub fn len(&self) -> i64 {
+        (self.end - self.start).max(0)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// The values contained in both intervals, [`None`] if they do not overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let intersection = Self::new(self.start.max(other.start), self.end.min(other.end));
+        (!intersection.is_empty()).then_some(intersection)
+    }
+
+    /// Moves the interval by `offset`.
+    #[must_use]
+    pub fn shift(&self, offset: i64) -> Self {
+        Self::new(self.start + offset, self.end + offset)
+    }
+}
+
+impl Display for Interval {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}..{}", self.start, self.end)
+    }
+}
+
+/// Sorts `intervals` and merges the ones that overlap or touch. Empty intervals are dropped.
+pub fn union(mut intervals: Vec<Interval>) -> Vec<Interval> {
+    intervals.retain(|i| !i.is_empty());
+    intervals.sort();
+
+    let mut merged: Vec<Interval> = vec![];
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        }
+    }
+
+    merged
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Maps integers by shifting every value of a source interval by the offset of that interval.
+/// Values outside of all source intervals map to themselves.
+///
+/// ```
+/// # use advent_of_code::interval::{Interval, IntervalMap};
+/// let mut map = IntervalMap::new();
+/// map.insert(Interval::new(10, 20), 100);
+/// assert_eq!(map.get(15), 115);
+/// assert_eq!(map.get(25), 25);
+/// assert_eq!(
+///     map.map_interval(Interval::new(5, 15)),
+///     vec![Interval::new(5, 10), Interval::new(110, 115)]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalMap {
+    /// Non-overlapping source intervals with their offsets, sorted by start.
+    entries: Vec<(Interval, i64)>,
+}
+
+impl IntervalMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps all values of `source` by `offset`.
+    ///
+    /// # Panics
+    /// Panics if `source` overlaps an interval that was inserted before, see [`IntervalMap::try_insert`].
+    pub fn insert(&mut self, source: Interval, offset: i64) {
+        if let Err(e) = self.try_insert(source, offset) {
+            panic!("{e}");
+        }
+    }
+
+    /// Maps all values of `source` by `offset`, or fails if `source` overlaps an interval that was
+    /// inserted before, e.g. for malformed inputs.
+    pub fn try_insert(&mut self, source: Interval, offset: i64) -> Result<(), OverlapError> {
+        if source.is_empty() {
+            return Ok(());
+        }
+
+        let index = self
+            .entries
+            .partition_point(|(i, _)| i.start < source.start);
+
+        let overlap = |i: usize| {
+            self.entries
+                .get(i)
+                .filter(|(other, _)| other.intersection(&source).is_some())
+                .map(|(other, _)| *other)
+        };
+        let existing = overlap(index).or_else(|| index.checked_sub(1).and_then(overlap));
+        if let Some(existing) = existing {
+            return Err(OverlapError { source, existing });
+        }
+
+        self.entries.insert(index, (source, offset));
+        Ok(())
+    }
+
+    /// The source intervals and their offsets, sorted by start.
+    pub fn entries(&self) -> &[(Interval, i64)] {
+        &self.entries
+    }
+
+    /// Maps a single value.
+    pub fn get(&self, value: i64) -> i64 {
+        let index = self.entries.partition_point(|(i, _)| i.end <= value);
+        match self.entries.get(index) {
+            Some((interval, offset)) if interval.contains(value) => value + offset,
+            _ => value,
+        }
+    }
+
+    /// Splits `interval` at the boundaries of the source intervals and maps every piece.
+    /// The pieces are returned in the order of their source values.
+    pub fn map_interval(&self, interval: Interval) -> Vec<Interval> {
+        self.split(interval)
+            .into_iter()
+            .map(|(piece, offset)| piece.shift(offset))
+            .collect()
+    }
+
+    /// Maps all `intervals` and merges the results, see [`union`].
+    pub fn map_intervals(&self, intervals: &[Interval]) -> Vec<Interval> {
+        union(
+            intervals
+                .iter()
+                .flat_map(|&interval| self.map_interval(interval))
+                .collect(),
+        )
+    }
+
+    /// Creates a map that behaves like applying `self` and then `next`.
+    #[must_use]
+    pub fn compose(&self, next: &IntervalMap) -> IntervalMap {
+        let everything = Interval::new(i64::MIN / 2, i64::MAX / 2);
+        let mut composed = IntervalMap::new();
+
+        for (piece, offset) in self.split(everything) {
+            for (next_piece, next_offset) in next.split(piece.shift(offset)) {
+                let total = offset + next_offset;
+                if total != 0 {
+                    composed.push_merged(next_piece.shift(-offset), total);
+                }
+            }
+        }
+
+        composed.entries.sort();
+        composed
+    }
+
+    /// Splits `interval` into pieces with the offset that applies to each piece, in order.
+    fn split(&self, interval: Interval) -> Vec<(Interval, i64)> {
+        let mut pieces = vec![];
+        let mut cursor = interval.start;
+
+        for (source, offset) in &self.entries {
+            if source.end <= cursor {
+                continue;
+            }
+            if source.start >= interval.end {
+                break;
+            }
+
+            if source.start > cursor {
+                pieces.push((Interval::new(cursor, source.start), 0));
+            }
+
+            let end = source.end.min(interval.end);
+            pieces.push((Interval::new(cursor.max(source.start), end), *offset));
+            cursor = end;
+        }
+
+        if cursor < interval.end {
+            pieces.push((Interval::new(cursor, interval.end), 0));
+        }
+
+        pieces
+    }
+
+    /// Appends an entry, extending the previous one if it ends at `source` with the same offset.
+    fn push_merged(&mut self, source: Interval, offset: i64) {
+        match self.entries.last_mut() {
+            Some((last, last_offset)) if last.end == source.start && *last_offset == offset => {
+                last.end = source.end;
+            }
+            _ => self.entries.push((source, offset)),
+        }
+    }
+}
+
+/// An interval that could not be inserted into an [`IntervalMap`] because it overlaps an existing one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OverlapError {
+    pub source: Interval,
+    pub existing: Interval,
+}
+
+impl Error for OverlapError {}
+
+impl Display for OverlapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "interval {} overlaps the existing interval {}",
+            self.source, self.existing
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{union, Interval, IntervalMap, OverlapError};
+
+    fn map(entries: &[(i64, i64, i64)]) -> IntervalMap {
+        let mut map = IntervalMap::new();
+        for &(start, end, offset) in entries {
+            map.insert(Interval::new(start, end), offset);
+        }
+        map
+    }
+
+    #[test]
+    fn intervals() {
+        let a = Interval::new(0, 10);
+        assert_eq!(a.len(), 10);
+        assert!(a.contains(0) && !a.contains(10));
+        assert_eq!(
+            a.intersection(&Interval::new(5, 15)),
+            Some(Interval::new(5, 10))
+        );
+        assert_eq!(a.intersection(&Interval::new(10, 15)), None);
+        assert_eq!(Interval::with_len(3, 2), Interval::new(3, 5));
+    }
+
+    #[test]
+    fn merges_intervals() {
+        assert_eq!(
+            union(vec![
+                Interval::new(8, 9),
+                Interval::new(0, 3),
+                Interval::new(3, 5),
+                Interval::new(4, 6),
+                Interval::new(7, 7),
+            ]),
+            vec![Interval::new(0, 6), Interval::new(8, 9)]
+        );
+    }
+
+    #[test]
+    fn maps_values() {
+        let map = map(&[(10, 20, 5), (0, 5, -1)]);
+        assert_eq!(map.get(-1), -1);
+        assert_eq!(map.get(0), -1);
+        assert_eq!(map.get(5), 5);
+        assert_eq!(map.get(19), 24);
+        assert_eq!(map.get(20), 20);
+    }
+
+    #[test]
+    fn splits_intervals() {
+        let map = map(&[(10, 20, 5), (0, 5, -1)]);
+        assert_eq!(
+            map.map_interval(Interval::new(-2, 25)),
+            vec![
+                Interval::new(-2, 0),
+                Interval::new(-1, 4),
+                Interval::new(5, 10),
+                Interval::new(15, 25),
+                Interval::new(20, 25),
+            ]
+        );
+        assert_eq!(
+            map.map_intervals(&[Interval::new(-2, 25)]),
+            vec![
+                Interval::new(-2, 4),
+                Interval::new(5, 10),
+                Interval::new(15, 25)
+            ]
+        );
+    }
+
+    #[test]
+    fn composes_maps() {
+        let first = map(&[(0, 10, 10), (20, 30, -20)]);
+        let second = map(&[(5, 15, 100)]);
+        let composed = first.compose(&second);
+
+        for value in -5..40 {
+            assert_eq!(composed.get(value), second.get(first.get(value)), "{value}");
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_on_overlapping_intervals() {
+        map(&[(0, 10, 1), (5, 15, 1)]);
+    }
+
+    #[test]
+    fn rejects_overlapping_intervals() {
+        let mut map = map(&[(0, 10, 1), (20, 30, 1)]);
+        assert_eq!(
+            map.try_insert(Interval::new(5, 15), 1),
+            Err(OverlapError {
+                source: Interval::new(5, 15),
+                existing: Interval::new(0, 10)
+            })
+        );
+        assert_eq!(
+            map.try_insert(Interval::new(15, 25), 1),
+            Err(OverlapError {
+                source: Interval::new(15, 25),
+                existing: Interval::new(20, 30)
+            })
+        );
+        assert_eq!(map.try_insert(Interval::new(10, 20), 1), Ok(()));
+        assert_eq!(map.entries().len(), 3);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -1,4 +1,14 @@
+pub mod cycles;
 mod day;
+mod day_set;
+pub mod grid;
+pub mod interval;
+pub mod parse;
+mod puzzle_id;
 pub mod template;
+mod year;
 
 pub use day::*;
+pub use day_set::*;
+pub use puzzle_id::*;
+pub use year::*;
@@ -1,30 +1,56 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use std::process::ExitCode;
+
+use advent_of_code::template::{
+    commands::{all, download, examples, read, scaffold, solve, verify, watch},
+    Error,
+};
 use args::{parse, AppArguments};
 
-mod args {
-    use std::process;
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
 
-    use advent_of_code::Day;
+mod args {
+    use advent_of_code::{
+        template::{
+            commands::{all, scaffold},
+            runner::OutputFormat,
+        },
+        DaySet, PuzzleId, Year,
+    };
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            template: String,
+            answer_type: String,
+            download: bool,
+            puzzle: PuzzleId,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             time: bool,
             submit: Option<u8>,
+            format: OutputFormat,
         },
         All {
-            release: bool,
-            time: bool,
+            year: Year,
+            options: all::Options,
+        },
+        Verify {
+            year: Year,
+        },
+        Watch {
+            puzzle: PuzzleId,
         },
     }
 
@@ -33,32 +59,51 @@ mod args {
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-                time: args.contains("--time"),
+                year: parse_year(&mut args)?,
+                options: all::Options {
+                    is_release: args.contains("--release"),
+                    is_timed: args.contains("--time"),
+                    is_compare: args.contains("--compare"),
+                    format: args.opt_value_from_str("--format")?.unwrap_or_default(),
+                    jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                    days: parse_days(&mut args)?,
+                    only_unsolved: args.contains("--only-unsolved"),
+                },
             },
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some("examples") => AppArguments::Examples {
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                template: args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_TEMPLATE.into()),
+                answer_type: args
+                    .opt_value_from_str("--answer-type")?
+                    .unwrap_or_else(|| scaffold::DEFAULT_ANSWER_TYPE.into()),
+                download: args.contains("--download"),
+                puzzle: parse_puzzle(&mut args)?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                puzzle: parse_puzzle(&mut args)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                format: args.opt_value_from_str("--format")?.unwrap_or_default(),
             },
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some("verify") => AppArguments::Verify {
+                year: parse_year(&mut args)?,
+            },
+            Some("watch") => AppArguments::Watch {
+                puzzle: parse_puzzle(&mut args)?,
+            },
+            Some(x) => return Err(format!("unknown command \"{x}\".").into()),
+            None => return Err("no command specified.".into()),
         };
 
         let remaining = args.finish();
@@ -68,25 +113,75 @@ mod args {
 
         Ok(app_args)
     }
+
+    /// Reads the year from `--year`, falling back to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        args.opt_value_from_str("--year")?
+            .or_else(Year::from_env)
+            .ok_or_else(|| "no year specified. Pass `--year <year>` or set AOC_YEAR.".into())
+    }
+
+    /// Reads the days selected by `--days`, `--skip` and `--since`. All days are selected by default.
+    fn parse_days(args: &mut pico_args::Arguments) -> Result<DaySet, Box<dyn std::error::Error>> {
+        let days: DaySet = args
+            .opt_value_from_str("--days")?
+            .unwrap_or_else(DaySet::all);
+        let skip: DaySet = args.opt_value_from_str("--skip")?.unwrap_or_default();
+        let days = days.difference(&skip);
+
+        Ok(match args.opt_value_from_str("--since")? {
+            Some(since) => days.since(since),
+            None => days,
+        })
+    }
+
+    /// Reads the day and the year of a puzzle.
+    fn parse_puzzle(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzleId, Box<dyn std::error::Error>> {
+        let year = parse_year(args)?;
+        Ok(PuzzleId::new(year, args.free_from_str()?))
+    }
 }
 
-fn main() {
-    match parse() {
+fn main() -> ExitCode {
+    let args = match parse() {
+        Ok(args) => args,
         Err(err) => {
             eprintln!("Error: {err}");
-            std::process::exit(1);
+            return Error::Usage(err.to_string()).exit_code();
         }
-        Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day } => scaffold::handle(day),
-            AppArguments::Solve {
-                day,
-                release,
-                time,
-                submit,
-            } => solve::handle(day, release, time, submit),
-        },
     };
+
+    let result = match args {
+        AppArguments::All { year, options } => all::handle(solutions::SOLUTIONS, year, &options),
+        AppArguments::Download { puzzle } => download::handle(puzzle),
+        AppArguments::Examples { puzzle } => examples::handle(puzzle),
+        AppArguments::Read { puzzle } => read::handle(puzzle),
+        AppArguments::Scaffold {
+            template,
+            answer_type,
+            download,
+            puzzle,
+        } => scaffold::handle(puzzle, &template, &answer_type, download),
+        AppArguments::Solve {
+            puzzle,
+            release,
+            time,
+            submit,
+            format,
+        } => solve::handle(puzzle, release, time, submit, format),
+        AppArguments::Verify { year } => verify::handle(solutions::SOLUTIONS, year),
+        AppArguments::Watch { puzzle } => watch::handle(puzzle),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            if !err.is_reported() {
+                eprintln!("Error: {err}");
+            }
+            err.exit_code()
+        }
+    }
 }
@@ -0,0 +1,581 @@
+use std::{fmt::Display, marker::PhantomData, str::FromStr};
+
+/// An error in the puzzle input, pointing at a line and optionally a column. Both are 1-based.
+///
+/// Parts can return `Result<T, InputError>`, the runner then reports the position of the error
+/// instead of a panic.
+///
+/// ```
+/// # use advent_of_code::parse::InputError;
+/// let input = "Game 1: 3 blue\nGame x: 4 red";
+/// let error = InputError::at(input, &input[20..21], "expected a game id");
+/// assert_eq!(error.to_string(), "line 2, column 6: expected a game id");
+/// ```
+#[derive(Debug, Clone, Eq)]
+pub struct InputError {
+    pub line: usize,
+    pub column: Option<usize>,
+    pub message: String,
+    /// The address of the character the error points at, see [`InputError::within`].
+    address: Option<usize>,
+}
+
+impl InputError {
+    /// Creates an error for the whole line `line`.
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            column: None,
+            message: message.into(),
+            address: None,
+        }
+    }
+
+    /// Points the error at `column` of its line.
+    #[must_use]
+    pub fn with_column(self, column: usize) -> Self {
+        Self {
+            column: Some(column),
+            ..self
+        }
+    }
+
+    /// Creates an error pointing at the start of `part`, which has to be a slice of `input`,
+    /// e.g. a line or a capture of a regex.
+    ///
+    /// # Panics
+    /// Panics if `part` does not point into `input`.
+    pub fn at(input: &str, part: &str, message: impl Into<String>) -> Self {
+        let address = part.as_ptr() as usize;
+        let offset = offset_in(input, address).expect("part is not a slice of input");
+
+        let before = &input[..offset];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+
+        Self {
+            address: Some(address),
+            ..Self::new(before.matches('\n').count() + 1, message)
+                .with_column(before[line_start..].chars().count() + 1)
+        }
+    }
+
+    /// Moves an error that was created with [`InputError::at`] on a slice of `input`, e.g. an error
+    /// of [`ints`] on a single line, to its position in `input`. Other errors are returned as they are.
+    #[must_use]
+    pub fn within(self, input: &str) -> Self {
+        match self.address {
+            Some(address) if offset_in(input, address).is_some() => {
+                // the address points into `input`, so it is a valid slice of it.
+                let offset = address - input.as_ptr() as usize;
+                Self::at(input, &input[offset..], self.message)
+            }
+            _ => self,
+        }
+    }
+}
+
+/// The offset of `address` in `input`, if it points into `input` or right after its end.
+fn offset_in(input: &str, address: usize) -> Option<usize> {
+    address
+        .checked_sub(input.as_ptr() as usize)
+        .filter(|&offset| offset <= input.len())
+}
+
+impl PartialEq for InputError {
+    fn eq(&self, other: &Self) -> bool {
+        (self.line, self.column, &self.message) == (other.line, other.column, &other.message)
+    }
+}
+
+impl std::error::Error for InputError {}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {column}: {}", self.line, self.message),
+            None => write!(f, "line {}: {}", self.line, self.message),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+// The helpers below report errors relative to the text they are given, which is usually a slice of
+// the input. [`lines`] moves them to their position in the whole input, see [`InputError::within`].
+
+/// Parses every line of `input` with `parse` and collects the results.
+/// Errors that point into the line are moved to their position in `input`, the line numbers of
+/// other errors are counted from the line.
+///
+/// ```
+/// # use advent_of_code::parse::{ints, lines};
+/// let histories: Vec<Vec<i64>> = lines("0 3 6\n-1 -2", |line| ints(line).collect()).unwrap();
+/// assert_eq!(histories, vec![vec![0, 3, 6], vec![-1, -2]]);
+/// ```
+pub fn lines<'a, T, C>(
+    input: &'a str,
+    mut parse: impl FnMut(&'a str) -> Result<T, InputError>,
+) -> Result<C, InputError>
+where
+    C: FromIterator<T>,
+{
+    input
+        .lines()
+        .map(|line| parse(line).map_err(|e| relocate(input, line, e)))
+        .collect()
+}
+
+/// Moves an error of parsing `part`, a slice of `input`, to its position in `input`.
+/// Errors that do not point into `part` count their line from the first line of `part`.
+fn relocate(input: &str, part: &str, error: InputError) -> InputError {
+    match error.address {
+        Some(_) => error.within(input),
+        None => InputError {
+            line: error.line + InputError::at(input, part, "").line - 1,
+            ..error
+        },
+    }
+}
+
+/// The integers in `text` with their sign, e.g. `x=-3, y=12` yields `-3` and `12`.
+/// All characters besides digits and a `-` right before digits separate the numbers.
+pub fn ints<T: FromStr>(text: &str) -> Ints<'_, T> {
+    Ints::new(text, true)
+}
+
+/// The unsigned integers in `text`. Unlike [`ints`], a `-` separates numbers, e.g. `1-3` yields `1` and `3`.
+pub fn uints<T: FromStr>(text: &str) -> Ints<'_, T> {
+    Ints::new(text, false)
+}
+
+/// Iterator over the numbers of a text, see [`ints`] and [`uints`].
+/// Yields an error for numbers that do not fit into `T`.
+#[derive(Debug, Clone)]
+pub struct Ints<'a, T> {
+    text: &'a str,
+    position: usize,
+    signed: bool,
+    number: PhantomData<T>,
+}
+
+impl<'a, T> Ints<'a, T> {
+    fn new(text: &'a str, signed: bool) -> Self {
+        Self {
+            text,
+            position: 0,
+            signed,
+            number: PhantomData,
+        }
+    }
+}
+
+impl<T> Iterator for Ints<'_, T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    type Item = Result<T, InputError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let bytes = self.text.as_bytes();
+        let is_digit = |i: usize| bytes.get(i).is_some_and(u8::is_ascii_digit);
+
+        let mut start = self.position;
+        while start < bytes.len() && !is_digit(start) {
+            start += 1;
+        }
+
+        let mut end = start;
+        while is_digit(end) {
+            end += 1;
+        }
+
+        if self.signed && start > self.position && bytes[start - 1] == b'-' {
+            start -= 1;
+        }
+
+        self.position = end;
+        if start == end {
+            return None;
+        }
+
+        let number = &self.text[start..end];
+        Some(number.parse().map_err(|e| {
+            InputError::at(
+                self.text,
+                number,
+                format!("invalid number \"{number}\": {e}"),
+            )
+        }))
+    }
+}
+
+/// Splits `input` into its sections, the blocks of lines separated by blank lines.
+/// Sections do not contain their trailing line break.
+///
+/// ```
+/// # use advent_of_code::parse::sections;
+/// let input = "seeds: 1 2\n\nseed-to-soil map:\n50 98 2\n";
+/// assert_eq!(sections(input).collect::<Vec<_>>(), vec!["seeds: 1 2", "seed-to-soil map:\n50 98 2"]);
+/// ```
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections { rest: input }
+}
+
+/// Iterator over the sections of a text, see [`sections`].
+#[derive(Debug, Clone)]
+pub struct Sections<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<&'a str> {
+        let mut start = None;
+        let mut end = 0;
+        let mut offset = 0;
+
+        for line in self.rest.split_inclusive('\n') {
+            match (start, line.trim().is_empty()) {
+                (None, true) => {}
+                (Some(_), true) => break,
+                (None, false) => {
+                    start = Some(offset);
+                    end = offset + line.len();
+                }
+                (Some(_), false) => end = offset + line.len(),
+            }
+            offset += line.len();
+        }
+
+        let Some(start) = start else {
+            self.rest = &self.rest[self.rest.len()..];
+            return None;
+        };
+
+        let section = &self.rest[start..end];
+        self.rest = &self.rest[end..];
+        Some(section.trim_end_matches(['\n', '\r']))
+    }
+}
+
+/// A puzzle input that consists of blocks of lines separated by blank lines: a header, e.g. the
+/// seeds of 2023 day 5, followed by sections. A section can be named by a first line that ends with
+/// a colon, e.g. `seed-to-soil map:`.
+///
+/// ```
+/// # use advent_of_code::parse::{ints, record, tuple, Input};
+/// let input = Input::new("seeds: 79 14\n\nseed-to-soil map:\n50 98 2\n52 50 48\n");
+///
+/// let seeds: Vec<i64> = input.header().unwrap().parse(|header| ints(record(header)?.1).collect()).unwrap();
+/// assert_eq!(seeds, vec![79, 14]);
+///
+/// let ranges: Vec<(i64, i64, i64)> = input.section("seed-to-soil").unwrap().parse_lines(tuple).unwrap();
+/// assert_eq!(ranges, vec![(50, 98, 2), (52, 50, 48)]);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Input<'a> {
+    text: &'a str,
+}
+
+impl<'a> Input<'a> {
+    pub fn new(text: &'a str) -> Self {
+        Self { text }
+    }
+
+    /// The first block of the input.
+    pub fn header(&self) -> Result<Section<'a>, InputError> {
+        sections(self.text)
+            .next()
+            .map(|block| Section::new(self.text, block))
+            .ok_or_else(|| InputError::new(1, "expected a header"))
+    }
+
+    /// The blocks after the header, in order.
+    pub fn sections(&self) -> impl Iterator<Item = Section<'a>> {
+        let text = self.text;
+        sections(text)
+            .skip(1)
+            .map(move |block| Section::new(text, block))
+    }
+
+    /// The section named `name`. The name may omit the last words of the name of the section,
+    /// e.g. `seed-to-soil` finds the section `seed-to-soil map:`.
+    pub fn section(&self, name: &str) -> Result<Section<'a>, InputError> {
+        let matches = |section: &Section| {
+            section.name.is_some_and(|n| {
+                n.strip_prefix(name)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+            })
+        };
+
+        self.sections().find(matches).ok_or_else(|| {
+            let end = &self.text[self.text.len()..];
+            InputError::at(self.text, end, format!("expected a section \"{name}\""))
+        })
+    }
+}
+
+/// A block of an [`Input`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// The name of the section without its colon, e.g. `seed-to-soil map`.
+    pub name: Option<&'a str>,
+    /// The lines of the section after its name.
+    pub body: &'a str,
+    input: &'a str,
+}
+
+impl<'a> Section<'a> {
+    fn new(input: &'a str, block: &'a str) -> Self {
+        let (first, rest) = block
+            .split_once('\n')
+            .unwrap_or((block, &block[block.len()..]));
+
+        match first.trim_end().strip_suffix(':') {
+            Some(name) => Self {
+                name: Some(name.trim()),
+                body: rest,
+                input,
+            },
+            None => Self {
+                name: None,
+                body: block,
+                input,
+            },
+        }
+    }
+
+    /// The lines of the body.
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.body.lines()
+    }
+
+    /// Parses the body with `parse`. Errors are moved to their position in the whole input.
+    pub fn parse<T>(
+        &self,
+        parse: impl FnOnce(&'a str) -> Result<T, InputError>,
+    ) -> Result<T, InputError> {
+        parse(self.body).map_err(|e| relocate(self.input, self.body, e))
+    }
+
+    /// Parses every line of the body with `parse`, see [`lines`].
+    /// Errors are moved to their position in the whole input.
+    pub fn parse_lines<T, C: FromIterator<T>>(
+        &self,
+        parse: impl FnMut(&'a str) -> Result<T, InputError>,
+    ) -> Result<C, InputError> {
+        self.parse(|body| lines(body, parse))
+    }
+}
+
+/// Splits a `key: values` record at its first colon, e.g. `Card 1: 41 48` into `("Card 1", "41 48")`.
+/// Both parts are trimmed.
+pub fn record(text: &str) -> Result<(&str, &str), InputError> {
+    let (key, values) = text
+        .split_once(':')
+        .ok_or_else(|| InputError::at(text, text, "expected \"<key>: <values>\""))?;
+    Ok((key.trim(), values.trim()))
+}
+
+/// Parses the fields of `text` into a tuple, e.g. `50 98 2` into `(50, 98, 2)`.
+/// Fields are separated by whitespace or commas and parsed with [`FromStr`].
+///
+/// ```
+/// # use advent_of_code::parse::tuple;
+/// let (name, x, y): (String, i32, i32) = tuple("robot 3, -4").unwrap();
+/// assert_eq!((name.as_str(), x, y), ("robot", 3, -4));
+/// assert!(tuple::<(u8, u8)>("1 2 3").is_err());
+/// ```
+pub fn tuple<T: Tuple>(text: &str) -> Result<T, InputError> {
+    T::parse_fields(text)
+}
+
+/// Tuples that can be parsed by [`tuple`].
+pub trait Tuple: Sized {
+    /// Parses the fields of `text`, see [`tuple`].
+    fn parse_fields(text: &str) -> Result<Self, InputError>;
+}
+
+macro_rules! impl_tuple {
+    ($len:literal: $($field:ident),+) => {
+        impl<$($field),+> Tuple for ($($field,)+)
+        where
+            $($field: FromStr, <$field as FromStr>::Err: Display),+
+        {
+            fn parse_fields(text: &str) -> Result<Self, InputError> {
+                let mut fields = text
+                    .split(|c: char| c.is_whitespace() || c == ',')
+                    .filter(|field| !field.is_empty());
+
+                let tuple = ($(parse_field::<$field>(text, fields.next(), $len)?,)+);
+
+                match fields.next() {
+                    Some(extra) => Err(InputError::at(text, extra, format!("expected {} fields", $len))),
+                    None => Ok(tuple),
+                }
+            }
+        }
+    };
+}
+
+impl_tuple!(1: A);
+impl_tuple!(2: A, B);
+impl_tuple!(3: A, B, C);
+impl_tuple!(4: A, B, C, D);
+impl_tuple!(5: A, B, C, D, E);
+impl_tuple!(6: A, B, C, D, E, F);
+
+fn parse_field<T>(text: &str, field: Option<&str>, len: usize) -> Result<T, InputError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    let field = field.ok_or_else(|| {
+        InputError::at(text, &text[text.len()..], format!("expected {len} fields"))
+    })?;
+
+    field
+        .parse()
+        .map_err(|e| InputError::at(text, field, format!("invalid value \"{field}\": {e}")))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ints, lines, record, sections, tuple, uints, Input, InputError};
+
+    #[test]
+    fn locates_errors() {
+        let input = "abc\nde🎄f\n\ng";
+        let line = input.lines().nth(1).unwrap();
+
+        assert_eq!(
+            InputError::at(input, line, "x"),
+            InputError::new(2, "x").with_column(1)
+        );
+        assert_eq!(InputError::at(input, &line[6..], "x").column, Some(4));
+        assert_eq!(InputError::at(input, &input[13..], "x").line, 4);
+        assert_eq!(
+            InputError::at(input, &input[..0], "x").to_string(),
+            "line 1, column 1: x"
+        );
+        assert_eq!(InputError::new(3, "x").to_string(), "line 3: x");
+    }
+
+    #[test]
+    fn moves_errors_into_the_input() {
+        let input = "1 2\n3 x 4";
+        let line = input.lines().nth(1).unwrap();
+        let numbers = &line[2..];
+        let error = InputError::at(numbers, numbers, "x").within(input);
+        assert_eq!(error, InputError::new(2, "x").with_column(3));
+        assert_eq!(InputError::new(2, "x").within(input).line, 2);
+
+        let parsed: Result<Vec<u8>, _> = lines(input, |_| Err(InputError::new(1, "x")));
+        assert_eq!(parsed, Err(InputError::new(1, "x")));
+        let parsed: Result<Vec<u8>, _> = lines(input, |line| match line {
+            "1 2" => Ok(1),
+            _ => Err(InputError::new(1, "x")),
+        });
+        assert_eq!(parsed, Err(InputError::new(2, "x")));
+    }
+
+    #[test]
+    fn parses_numbers() {
+        let numbers: Vec<i64> = ints("x=-3, y=12 -a 5-3").collect::<Result<_, _>>().unwrap();
+        assert_eq!(numbers, vec![-3, 12, 5, -3]);
+
+        let numbers: Vec<u32> = uints("Card  1: 1-3").collect::<Result<_, _>>().unwrap();
+        assert_eq!(numbers, vec![1, 1, 3]);
+
+        assert_eq!(ints::<u8>("").next(), None);
+        assert_eq!(
+            ints::<u8>("1 300").nth(1).unwrap().unwrap_err().to_string(),
+            "line 1, column 3: invalid number \"300\": number too large to fit in target type"
+        );
+        assert!(ints::<u8>("-1").next().unwrap().is_err());
+    }
+
+    #[test]
+    fn parses_lines() {
+        let parsed: Result<Vec<Vec<u8>>, _> = lines("1 2\n3 300", |line| ints(line).collect());
+        assert_eq!(
+            parsed.unwrap_err().to_string(),
+            "line 2, column 3: invalid number \"300\": number too large to fit in target type"
+        );
+    }
+
+    #[test]
+    fn splits_sections() {
+        let input = "\na\nb\n\n  \nc\r\n\r\nd";
+        assert_eq!(sections(input).collect::<Vec<_>>(), vec!["a\nb", "c", "d"]);
+        assert_eq!(sections("\n\n").next(), None);
+    }
+
+    #[test]
+    fn reads_sections() {
+        let text = "LR\n\nfirst:\na\nb\n\nsecond map:\n\nthird";
+        let input = Input::new(text);
+
+        assert_eq!(input.header().unwrap().body, "LR");
+        assert_eq!(input.header().unwrap().name, None);
+
+        let names: Vec<Option<&str>> = input.sections().map(|s| s.name).collect();
+        assert_eq!(names, vec![Some("first"), Some("second map"), None]);
+
+        let first = input.section("first").unwrap();
+        assert_eq!(first.lines().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(input.section("second").unwrap().body, "");
+        assert!(input.section("fir").is_err());
+        assert!(Input::new("\n").header().is_err());
+
+        let error = first
+            .parse_lines::<u8, Vec<_>>(|line| tuple::<(u8,)>(line).map(|(x,)| x))
+            .unwrap_err();
+        assert_eq!((error.line, error.column), (4, Some(1)));
+
+        let error = first
+            .parse_lines::<u8, Vec<_>>(|_| Err(InputError::new(2, "x")))
+            .unwrap_err();
+        assert_eq!(error.line, 5);
+    }
+
+    #[test]
+    fn parses_records() {
+        assert_eq!(record("Card 1:  41 48 ").unwrap(), ("Card 1", "41 48"));
+        assert_eq!(
+            record("seed-to-soil map:").unwrap(),
+            ("seed-to-soil map", "")
+        );
+        assert!(record("50 98 2").is_err());
+    }
+
+    #[test]
+    fn parses_tuples() {
+        assert_eq!(tuple::<(i64, i64, i64)>("50 98 2"), Ok((50, 98, 2)));
+        assert_eq!(tuple::<(char, u8)>("R,4"), Ok(('R', 4)));
+        assert_eq!(
+            tuple::<(u8, u8)>("1").unwrap_err().to_string(),
+            "line 1, column 2: expected 2 fields"
+        );
+        assert_eq!(
+            tuple::<(u8, u8)>("1 2 3").unwrap_err().to_string(),
+            "line 1, column 5: expected 2 fields"
+        );
+        assert_eq!(
+            tuple::<(u8,)>("x").unwrap_err().to_string(),
+            "line 1, column 1: invalid value \"x\": invalid digit found in string"
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_foreign_slices() {
+        InputError::at("abc", &String::from("abc"), "x");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,85 @@
+use std::fmt::Display;
+
+use crate::{Day, Year};
+
+/// Identifies a puzzle by its year and day.
+///
+/// # Display
+/// This value displays as the year and day joined by a dash, which is also the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::{Day, PuzzleId, Year};
+/// let puzzle = PuzzleId::new(Year::new(2023).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(puzzle.to_string(), "2023-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Path of a data file of this puzzle, e.g. `data/2023/inputs/08.txt` for the folder `inputs`.
+    pub fn data_path(&self, folder: &str, extension: &str) -> String {
+        format!("data/{}/{folder}/{}.{extension}", self.year, self.day)
+    }
+
+    /// Path of the personal puzzle input.
+    pub fn input_path(&self) -> String {
+        self.data_path("inputs", "txt")
+    }
+
+    /// Path of the example input. If `part` is passed, the example of that part, e.g. `data/2023/examples/08-2.txt`.
+    pub fn example_path(&self, part: Option<u8>) -> String {
+        match part {
+            Some(part) => format!("data/{}/examples/{}-{part}.txt", self.year, self.day),
+            None => self.data_path("examples", "txt"),
+        }
+    }
+
+    /// Path of the stored answer of `part`, e.g. `data/2023/answers/08-1.txt`.
+    pub fn answer_path(&self, part: u8) -> String {
+        format!("data/{}/answers/{}-{part}.txt", self.year, self.day)
+    }
+
+    /// Path of the puzzle description as written by `download`.
+    pub fn puzzle_path(&self) -> String {
+        self.data_path("puzzles", "md")
+    }
+
+    /// Path of the solution module, e.g. `src/bin/2023-08.rs`.
+    pub fn module_path(&self) -> String {
+        format!("src/bin/{self}.rs")
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::PuzzleId;
+    use crate::{day, year};
+
+    #[test]
+    fn puzzle_paths() {
+        let puzzle = PuzzleId::new(year!(2022), day!(5));
+        assert_eq!(puzzle.input_path(), "data/2022/inputs/05.txt");
+        assert_eq!(puzzle.example_path(None), "data/2022/examples/05.txt");
+        assert_eq!(puzzle.example_path(Some(2)), "data/2022/examples/05-2.txt");
+        assert_eq!(puzzle.answer_path(1), "data/2022/answers/05-1.txt");
+        assert_eq!(puzzle.puzzle_path(), "data/2022/puzzles/05.md");
+        assert_eq!(puzzle.module_path(), "src/bin/2022-05.rs");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,103 @@
+/// Module that stores the accepted answers of solved parts, one file per part, e.g. `data/2023/answers/08-1.txt`.
+/// Answers are stored when a submission is accepted and can also be written by hand.
+/// `verify` checks that solutions still produce these answers.
+use std::{fmt::Display, fs, io, path::Path};
+
+use crate::template::readme_benchmarks::PartStatus;
+use crate::PuzzleId;
+
+/// Reads the stored answer of a part, [`None`] if no answer is stored.
+pub fn load(puzzle: PuzzleId, part: u8) -> io::Result<Option<String>> {
+    match fs::read_to_string(puzzle.answer_path(part)) {
+        Ok(answer) => Ok(Some(answer.trim_end().to_string()).filter(|x| !x.is_empty())),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Whether both parts of `puzzle` have a stored answer.
+pub fn is_solved(puzzle: PuzzleId) -> bool {
+    [1, 2]
+        .into_iter()
+        .all(|part| matches!(load(puzzle, part), Ok(Some(_))))
+}
+
+/// Stores `answer` as the answer of a part, replacing a previously stored answer.
+pub fn store(puzzle: PuzzleId, part: u8, answer: &str) -> io::Result<()> {
+    let path = puzzle.answer_path(part);
+    if let Some(parent) = Path::new(&path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, format!("{answer}\n"))
+}
+
+/// Why a part did not produce its stored answer.
+#[derive(Debug, PartialEq)]
+pub enum Mismatch {
+    /// The part returned a different answer.
+    Answer { expected: String, found: String },
+    /// The part did not return an answer, contains the status of the part.
+    Status { expected: String, found: String },
+}
+
+impl Display for Mismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Mismatch::Answer { expected, found } => write!(f, "expected {expected}, got {found}"),
+            Mismatch::Status { expected, found } => write!(f, "expected {expected}, part {found}"),
+        }
+    }
+}
+
+/// Compares the result of a part to its stored answer. Trailing whitespace is ignored.
+pub fn verify(expected: &str, status: &PartStatus) -> Result<(), Mismatch> {
+    match status {
+        PartStatus::Solved { answer, .. } if answer.trim_end() == expected.trim_end() => Ok(()),
+        PartStatus::Solved { answer, .. } => Err(Mismatch::Answer {
+            expected: expected.into(),
+            found: answer.clone(),
+        }),
+        status => Err(Mismatch::Status {
+            expected: expected.into(),
+            found: status.name().replace('_', " "),
+        }),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{verify, Mismatch};
+    use crate::template::{readme_benchmarks::PartStatus, stats::Stats};
+    use std::time::Duration;
+
+    fn solved(answer: &str) -> PartStatus {
+        PartStatus::Solved {
+            answer: answer.into(),
+            stats: Stats::single(Duration::from_millis(1)),
+        }
+    }
+
+    #[test]
+    fn verifies_answers() {
+        assert_eq!(verify("42", &solved("42")), Ok(()));
+        assert_eq!(verify("#.\n.#\n", &solved("#.\n.#")), Ok(()));
+        assert_eq!(
+            verify("42", &solved("43")),
+            Err(Mismatch::Answer {
+                expected: "42".into(),
+                found: "43".into()
+            })
+        );
+        assert_eq!(
+            verify("42", &PartStatus::TimedOut(Duration::from_secs(1)))
+                .unwrap_err()
+                .to_string(),
+            "expected 42, part timed out"
+        );
+        assert!(verify("42", &PartStatus::Unsolved).is_err());
+    }
+}
+
+/* -------------------------------------------------------------------------- */
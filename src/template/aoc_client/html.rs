@@ -0,0 +1,271 @@
+/// Just enough HTML handling to turn Advent of Code pages into markdown and plain text.
+
+#[derive(Debug, PartialEq, Eq)]
+enum Token<'a> {
+    Text(&'a str),
+    Open { name: String, href: Option<String> },
+    Close(String),
+}
+
+/// Splits `html` into text, opening and closing tags. Comments and doctypes are dropped.
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let Some(start) = rest.find('<') else {
+            tokens.push(Token::Text(rest));
+            break;
+        };
+
+        if start > 0 {
+            tokens.push(Token::Text(&rest[..start]));
+        }
+
+        let Some(end) = rest[start..].find('>').map(|i| start + i) else {
+            tokens.push(Token::Text(&rest[start..]));
+            break;
+        };
+
+        let tag = &rest[start + 1..end];
+        rest = &rest[end + 1..];
+
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_ascii_lowercase()));
+        } else if !tag.starts_with('!') {
+            let tag = tag.trim_end_matches('/');
+            let name = tag
+                .split_whitespace()
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+            tokens.push(Token::Open {
+                name,
+                href: attribute(tag, "href"),
+            });
+        }
+    }
+
+    tokens
+}
+
+fn attribute(tag: &str, name: &str) -> Option<String> {
+    let start = tag.find(&format!("{name}=\""))? + name.len() + 2;
+    let end = tag[start..].find('"')? + start;
+    Some(decode_entities(&tag[start..end]))
+}
+
+/// Decodes the named and numeric character references that occur on Advent of Code pages.
+#[must_use]
+pub fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "lt" => '<',
+                "gt" => '>',
+                "amp" => '&',
+                "quot" => '"',
+                "apos" => '\'',
+                "nbsp" => ' ',
+                _ => {
+                    let code = match entity.strip_prefix("#x") {
+                        Some(hex) => u32::from_str_radix(hex, 16).ok()?,
+                        None => entity.strip_prefix('#')?.parse().ok()?,
+                    };
+                    char::from_u32(code)?
+                }
+            };
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// Returns the inner HTML of every `<article>` element.
+#[must_use]
+pub fn articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = rest[content_start..]
+            .find("</article>")
+            .map(|i| content_start + i)
+        else {
+            break;
+        };
+
+        articles.push(&rest[content_start..end]);
+        rest = &rest[end..];
+    }
+
+    articles
+}
+
+/// Converts a puzzle description to markdown.
+/// Code blocks are fenced, inline code is wrapped in backticks and emphasis in asterisks, e.g. `<code><em>42</em></code>` becomes `` `*42*` ``.
+#[must_use]
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut in_pre = false;
+    let mut links: Vec<Option<String>> = vec![];
+
+    for token in tokenize(html) {
+        match token {
+            Token::Text(text) => {
+                let text = decode_entities(text);
+                // drop whitespace between blocks.
+                if !in_pre && (out.is_empty() || out.ends_with("\n\n")) {
+                    out.push_str(text.trim_start());
+                } else {
+                    out.push_str(&text);
+                }
+            }
+            // tags inside code blocks are only used for highlighting.
+            Token::Open { name, .. } if in_pre && name != "pre" => {}
+            Token::Close(name) if in_pre && name != "pre" => {}
+            Token::Open { name, href } => match name.as_str() {
+                "h2" => out.push_str("## "),
+                "pre" => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                "code" => out.push('`'),
+                "em" => out.push('*'),
+                "li" => out.push_str("- "),
+                "a" => {
+                    if href.is_some() {
+                        out.push('[');
+                    }
+                    links.push(href);
+                }
+                _ => {}
+            },
+            Token::Close(name) => match name.as_str() {
+                "h2" | "p" => out.push_str("\n\n"),
+                "pre" => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```\n\n");
+                }
+                "code" => out.push('`'),
+                "em" => out.push('*'),
+                "li" => out.push('\n'),
+                "ul" => out.push('\n'),
+                "a" => {
+                    if let Some(Some(href)) = links.pop() {
+                        out.push_str(&format!("]({href})"));
+                    }
+                }
+                _ => {}
+            },
+        }
+    }
+
+    let mut markdown = out.trim().to_string();
+    markdown.push('\n');
+    markdown
+}
+
+/// Strips all tags from `html` and collapses whitespace.
+#[must_use]
+pub fn to_text(html: &str) -> String {
+    let text: String = tokenize(html)
+        .into_iter()
+        .filter_map(|token| match token {
+            Token::Text(text) => Some(decode_entities(text)),
+            _ => None,
+        })
+        .collect();
+
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{articles, decode_entities, to_markdown, to_text};
+
+    #[test]
+    fn decodes_entities() {
+        assert_eq!(
+            decode_entities("a &lt;b&gt; &amp; &quot;c&quot; &#39;d&#x27; &foo; &"),
+            "a <b> & \"c\" 'd' &foo; &"
+        );
+    }
+
+    #[test]
+    fn extracts_articles() {
+        let html = r#"<main><article class="day-desc"><h2>One</h2></article><p>x</p><article><p>Two</p></article></main>"#;
+        assert_eq!(articles(html), vec!["<h2>One</h2>", "<p>Two</p>"]);
+    }
+
+    #[test]
+    fn converts_puzzle_to_markdown() {
+        let html = concat!(
+            r#"<h2>--- Day 1: Trebuchet?! ---</h2>"#,
+            r#"<p>Something is <em>wrong</em> with <a href="/2023/about">global</a> snow.</p>"#,
+            r#"<p>For example:</p>"#,
+            "<pre><code>1abc2\npqr3stu8vwx\n<em>x</em>y\n</code></pre>\n",
+            r#"<ul><li><code>a &lt; b</code></li><li>c</li></ul>"#,
+            r#"<p>Adding these together produces <code><em>142</em></code>.</p>"#,
+        );
+
+        let expected = [
+            "## --- Day 1: Trebuchet?! ---",
+            "",
+            "Something is *wrong* with [global](/2023/about) snow.",
+            "",
+            "For example:",
+            "",
+            "```",
+            "1abc2",
+            "pqr3stu8vwx",
+            "xy",
+            "```",
+            "",
+            "- `a < b`",
+            "- c",
+            "",
+            "Adding these together produces `*142*`.",
+            "",
+        ]
+        .join("\n");
+
+        assert_eq!(to_markdown(html), expected);
+    }
+
+    #[test]
+    fn converts_to_text() {
+        let html =
+            "<p>That's not the right answer; your answer is too <em>high</em>.\n  Please wait.</p>";
+        assert_eq!(
+            to_text(html),
+            "That's not the right answer; your answer is too high. Please wait."
+        );
+    }
+}
@@ -0,0 +1,451 @@
+/// Minimal client for the Advent of Code website.
+/// Downloads inputs and puzzle descriptions and submits answers using the session cookie of the user.
+use std::{env, fmt::Display, fs, io, path::PathBuf, time::Duration};
+
+use crate::PuzzleId;
+
+pub mod html;
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = concat!(
+    "github.com/rlecaro2/advent-of-code-rust (advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    ")"
+);
+
+#[derive(Debug)]
+pub enum Error {
+    /// No session cookie was found in `AOC_SESSION` or `~/.adventofcode.session`.
+    MissingSession,
+    /// The website responded with a 4xx status, e.g. because the session expired or the day is not unlocked yet.
+    Client {
+        status: u16,
+        message: String,
+    },
+    /// The website responded with a 5xx status.
+    Server {
+        status: u16,
+    },
+    /// The request could not be sent or the response could not be read.
+    Transport(String),
+    /// The response did not have the expected shape.
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingSession => write!(
+                f,
+                "no session cookie found. Set AOC_SESSION or create a \".adventofcode.session\" file in your home directory."
+            ),
+            Error::Client { status, message } => {
+                write!(f, "request was rejected with status {status}: {message}")
+            }
+            Error::Server { status } => {
+                write!(f, "adventofcode.com is unavailable (status {status}).")
+            }
+            Error::Transport(e) => write!(f, "request failed: {e}"),
+            Error::Parser(e) => write!(f, "unexpected response: {e}"),
+            Error::IO(e) => write!(f, "could not read response: {e}"),
+        }
+    }
+}
+
+/// The outcome of submitting an answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, with the time left to wait if it was reported.
+    RateLimited(Option<Duration>),
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+    /// The response could not be interpreted, contains the text of the response.
+    Unknown(String),
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "That's the right answer!"),
+            Verdict::Incorrect => write!(f, "That's not the right answer."),
+            Verdict::TooHigh => write!(f, "That's not the right answer, it is too high."),
+            Verdict::TooLow => write!(f, "That's not the right answer, it is too low."),
+            Verdict::RateLimited(Some(wait)) => write!(
+                f,
+                "You gave an answer too recently, wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            Verdict::RateLimited(None) => write!(f, "You gave an answer too recently."),
+            Verdict::WrongLevel => write!(
+                f,
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Verdict::Unknown(text) => write!(f, "{text}"),
+        }
+    }
+}
+
+impl Verdict {
+    /// The verdict as used in the submission ledger and JSON records, e.g. `too_high`.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::RateLimited(_) => "rate_limited",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::Unknown(_) => "unknown",
+        }
+    }
+}
+
+/// The public url of the description of `puzzle`, e.g. `https://adventofcode.com/2023/day/1`.
+#[must_use]
+pub fn puzzle_url(puzzle: PuzzleId) -> String {
+    format!(
+        "{DEFAULT_BASE_URL}/{}/day/{}",
+        puzzle.year,
+        puzzle.day.into_inner()
+    )
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    #[must_use]
+    pub fn new(base_url: &str, session: &str) -> Self {
+        let agent = ureq::AgentBuilder::new()
+            .user_agent(USER_AGENT)
+            .timeout(Duration::from_secs(30))
+            .build();
+
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.trim().to_string(),
+            agent,
+        }
+    }
+
+    /// Creates a client from the environment:
+    ///  1. the session cookie is read from `AOC_SESSION` or `~/.adventofcode.session`.
+    ///  2. the base url can be overridden with `AOC_BASE_URL`.
+    pub fn from_env() -> Result<Self, Error> {
+        let session = env::var("AOC_SESSION")
+            .ok()
+            .or_else(|| fs::read_to_string(get_session_path()?).ok())
+            .filter(|x| !x.trim().is_empty())
+            .ok_or(Error::MissingSession)?;
+
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+
+        Ok(Self::new(&base_url, &session))
+    }
+
+    /// Downloads the input of `puzzle`.
+    pub fn get_input(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        let request = self.agent.get(&self.day_url(puzzle, "/input"));
+        self.send(request, None)
+    }
+
+    /// Downloads the description of `puzzle` and converts it to markdown.
+    /// The description of part two is only included once part one is solved.
+    pub fn get_puzzle(&self, puzzle: PuzzleId) -> Result<String, Error> {
+        let request = self.agent.get(&self.day_url(puzzle, ""));
+        let page = self.send(request, None)?;
+
+        let articles = html::articles(&page);
+        if articles.is_empty() {
+            return Err(Error::Parser("no puzzle description found.".into()));
+        }
+
+        Ok(articles
+            .iter()
+            .map(|article| html::to_markdown(article))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Submits `answer` for a part of `puzzle`.
+    pub fn submit(&self, puzzle: PuzzleId, part: u8, answer: &str) -> Result<Verdict, Error> {
+        let request = self.agent.post(&self.day_url(puzzle, "/answer"));
+        let level = part.to_string();
+        let page = self.send(request, Some(&[("level", &level), ("answer", answer)]))?;
+
+        let text = html::articles(&page)
+            .first()
+            .map_or_else(|| html::to_text(&page), |article| html::to_text(article));
+
+        Ok(parse_verdict(&text))
+    }
+
+    fn day_url(&self, puzzle: PuzzleId, suffix: &str) -> String {
+        format!(
+            "{}/{}/day/{}{suffix}",
+            self.base_url,
+            puzzle.year,
+            puzzle.day.into_inner()
+        )
+    }
+
+    fn send(&self, request: ureq::Request, form: Option<&[(&str, &str)]>) -> Result<String, Error> {
+        let request = request.set("Cookie", &format!("session={}", self.session));
+
+        let response = match form {
+            Some(form) => request.send_form(form),
+            None => request.call(),
+        };
+
+        match response {
+            Ok(response) => Ok(response.into_string()?),
+            Err(ureq::Error::Status(status, response)) if status >= 500 => {
+                let _ = response.into_string();
+                Err(Error::Server { status })
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(Error::Client {
+                    status,
+                    message: html::to_text(&body),
+                })
+            }
+            Err(ureq::Error::Transport(e)) => Err(Error::Transport(e.to_string())),
+        }
+    }
+}
+
+fn get_session_path() -> Option<PathBuf> {
+    let home = env::var_os("HOME").or_else(|| env::var_os("USERPROFILE"))?;
+    Some(PathBuf::from(home).join(".adventofcode.session"))
+}
+
+/// Interprets the text of the page returned after submitting an answer.
+fn parse_verdict(text: &str) -> Verdict {
+    if text.contains("That's the right answer") {
+        Verdict::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("too high") {
+            Verdict::TooHigh
+        } else if text.contains("too low") {
+            Verdict::TooLow
+        } else {
+            Verdict::Incorrect
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Verdict::RateLimited(parse_wait(text))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Verdict::WrongLevel
+    } else {
+        Verdict::Unknown(text.to_string())
+    }
+}
+
+/// Parses a wait time like "You have 1m 30s left to wait".
+fn parse_wait(text: &str) -> Option<Duration> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = text[start..].find(" left to wait")? + start;
+
+    text[start..end]
+        .split_whitespace()
+        .map(|part| {
+            let (value, unit) = part.split_at(part.find(|c: char| !c.is_ascii_digit())?);
+            let value: u64 = value.parse().ok()?;
+            match unit {
+                "h" => Some(value * 3600),
+                "m" => Some(value * 60),
+                "s" => Some(value),
+                _ => None,
+            }
+        })
+        .sum::<Option<u64>>()
+        .map(Duration::from_secs)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_verdict, AocClient, Error, Verdict};
+    use crate::{day, year, PuzzleId};
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        sync::mpsc,
+        thread,
+        time::Duration,
+    };
+
+    const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(1));
+
+    /// A request as received by the stub server.
+    struct Request {
+        head: String,
+        body: String,
+    }
+
+    /// Serves a single request with `status` and `body` on a local port. Returns the base url and a receiver for the request.
+    fn stub_server(status: u16, body: &'static str) -> (String, mpsc::Receiver<Request>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (tx, rx) = mpsc::channel();
+
+        thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+
+            let mut head = String::new();
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line.trim().is_empty() {
+                    break;
+                }
+                head.push_str(&line);
+            }
+
+            let length = head
+                .lines()
+                .find_map(|l| {
+                    l.to_ascii_lowercase()
+                        .strip_prefix("content-length:")
+                        .map(|x| x.trim().parse::<usize>().unwrap())
+                })
+                .unwrap_or(0);
+            let mut request_body = vec![0; length];
+            reader.read_exact(&mut request_body).unwrap();
+
+            let mut stream = stream;
+            write!(
+                stream,
+                "HTTP/1.1 {status} Stub\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            tx.send(Request {
+                head,
+                body: String::from_utf8(request_body).unwrap(),
+            })
+            .unwrap();
+        });
+
+        (url, rx)
+    }
+
+    #[test]
+    fn downloads_input() {
+        let (url, rx) = stub_server(200, "1abc2\npqr3stu8vwx\n");
+        let client = AocClient::new(&url, "secret");
+
+        assert_eq!(client.get_input(PUZZLE).unwrap(), "1abc2\npqr3stu8vwx\n");
+
+        let request = rx.recv().unwrap();
+        assert!(request.head.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.head.contains("session=secret"));
+    }
+
+    #[test]
+    fn downloads_puzzle() {
+        let (url, _rx) = stub_server(
+            200,
+            r#"<html><main><article class="day-desc"><h2>--- Day 1: Trebuchet?! ---</h2><p>Hi.</p></article><p>Other</p></main></html>"#,
+        );
+        let client = AocClient::new(&url, "secret");
+
+        assert_eq!(
+            client.get_puzzle(PUZZLE).unwrap(),
+            "## --- Day 1: Trebuchet?! ---\n\nHi.\n"
+        );
+    }
+
+    #[test]
+    fn submits_answer() {
+        let (url, rx) = stub_server(
+            200,
+            "<main><article><p>That's the right answer! You are one gold star closer.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret");
+
+        assert_eq!(
+            client
+                .submit(PuzzleId::new(year!(2023), day!(8)), 2, "42")
+                .unwrap(),
+            Verdict::Correct
+        );
+
+        let request = rx.recv().unwrap();
+        assert!(request.head.starts_with("POST /2023/day/8/answer HTTP/1.1"));
+        assert_eq!(request.body, "level=2&answer=42");
+    }
+
+    #[test]
+    fn errors_on_client_error() {
+        let (url, _rx) = stub_server(400, "<p>Puzzle inputs differ by user.  Please log in.</p>");
+        let client = AocClient::new(&url, "expired");
+
+        match client.get_input(PUZZLE) {
+            Err(Error::Client { status, message }) => {
+                assert_eq!(status, 400);
+                assert_eq!(message, "Puzzle inputs differ by user. Please log in.");
+            }
+            other => panic!("unexpected result: {other:?}"),
+        }
+    }
+
+    #[test]
+    fn errors_on_server_error() {
+        let (url, _rx) = stub_server(503, "");
+        let client = AocClient::new(&url, "secret");
+        assert!(matches!(
+            client.get_input(PUZZLE),
+            Err(Error::Server { status: 503 })
+        ));
+    }
+
+    #[test]
+    fn parses_verdicts() {
+        assert_eq!(
+            parse_verdict(
+                "That's not the right answer; your answer is too high. Please wait one minute."
+            ),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            parse_verdict("That's not the right answer; your answer is too low."),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            parse_verdict("That's not the right answer. If you're stuck, ..."),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            parse_verdict("You gave an answer too recently; you have to wait after submitting an answer before trying again. You have 1m 5s left to wait."),
+            Verdict::RateLimited(Some(Duration::from_secs(65)))
+        );
+        assert_eq!(
+            parse_verdict("You gave an answer too recently."),
+            Verdict::RateLimited(None)
+        );
+        assert_eq!(
+            parse_verdict(
+                "You don't seem to be solving the right level. Did you already complete it?"
+            ),
+            Verdict::WrongLevel
+        );
+        assert_eq!(parse_verdict("???"), Verdict::Unknown("???".into()));
+    }
+}
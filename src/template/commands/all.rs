@@ -1,254 +1,451 @@
-use std::io;
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    mpsc, Arc,
+};
+use std::{fmt::Display, fs, io, panic, thread};
 
 use crate::template::{
-    readme_benchmarks::{self, Timings},
+    self, answers,
+    history::{self, Comparison, Part},
+    output,
+    readme_benchmarks::{self, PartStatus, Timings},
+    runner::{self, part_record, puzzle_record, OutputFormat, Solution},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
-use crate::{all_days, Day};
+use crate::{DaySet, PuzzleId, Year};
+
+/// Options of the `all` command.
+#[derive(Clone, Debug)]
+pub struct Options {
+    pub is_release: bool,
+    pub is_timed: bool,
+    pub is_compare: bool,
+    pub format: OutputFormat,
+    /// The number of days that are solved at the same time.
+    pub jobs: usize,
+    /// The days to run.
+    pub days: DaySet,
+    /// Only runs days with a part that has no stored answer yet.
+    pub only_unsolved: bool,
+}
+
+/// Runs the `solutions` of the selected days of `year` in-process. Days without a solution are reported as not solved.
+///
+/// If `jobs` is larger than one, days are solved on that many threads. The output of each day is
+/// captured and printed in order once the day is done. Benchmarks never run in parallel, see [`crate::template::runner`].
+///
+/// Timed release runs are appended to the benchmark history and written to the readme.
+/// If `is_compare` is set, timings are instead compared to the history and [`template::Error::Failed`]
+/// is returned if any part got significantly slower.
+///
+/// Like a solution binary, fails with [`template::Error::Failed`] if a part panicked, failed or timed out
+/// and with [`template::Error::NotSolved`] if a part returned no answer.
+///
+/// A part that timed out keeps running in the background until the process exits. The timings of the
+/// days that ran meanwhile are reported as unreliable, and no timings of the run are stored or compared.
+pub fn handle(
+    solutions: &[Solution],
+    year: Year,
+    options: &Options,
+) -> Result<(), template::Error> {
+    let Options {
+        is_release,
+        is_timed,
+        is_compare,
+        format,
+        jobs,
+        days,
+        only_unsolved,
+    } = *options;
 
-pub fn handle(is_release: bool, is_timed: bool) {
     let mut timings: Vec<Timings> = vec![];
+    let mut unreliable_days = 0;
+    let is_human = format == OutputFormat::Human;
+
+    let days: Vec<(PuzzleId, Option<&Solution>)> = days
+        .iter()
+        .map(|day| PuzzleId::new(year, day))
+        .filter(|&puzzle| !only_unsolved || !answers::is_solved(puzzle))
+        .map(|puzzle| (puzzle, solutions.iter().find(|s| s.puzzle == puzzle)))
+        .collect();
+
+    if days.is_empty() {
+        return Err(template::Error::Usage("no days selected.".into()));
+    }
 
-    all_days().for_each(|day| {
-        if day > 1 {
-            println!();
-        }
+    thread::scope(|scope| {
+        let mut parallel = (jobs > 1).then(|| Parallel::spawn(scope, &days, jobs));
+
+        for (index, (puzzle, solution)) in days.iter().enumerate() {
+            let day = puzzle.day;
 
-        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-        println!("------");
+            if is_human {
+                if index > 0 {
+                    println!();
+                }
+
+                println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+                println!("------");
+            }
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            let Some(solution) = solution else {
+                match format {
+                    OutputFormat::Human => println!("Not solved."),
+                    OutputFormat::Json => print_records(*puzzle, "missing", None),
+                }
+                continue;
+            };
+
+            let result = match &mut parallel {
+                Some(parallel) => {
+                    let (result, output) = parallel.take(index);
+                    print!("{output}");
+                    result
+                }
+                None => run_solution(solution),
+            };
+
+            if runner::running_timed_out_parts() > 0 {
+                unreliable_days += 1;
+                eprintln!(
+                    "Warning: a part that timed out is still running, the timings of day {day} are unreliable."
+                );
+            }
 
-        if output.is_empty() {
-            println!("Not solved.");
-        } else {
-            let val = child_commands::parse_exec_time(&output, day);
-            timings.push(val);
+            match result {
+                Ok(val) => timings.push(val),
+                Err(Error::IO(e)) => match format {
+                    OutputFormat::Human => {
+                        eprintln!("Could not read input file for day {day}: {e}");
+                    }
+                    OutputFormat::Json => print_records(*puzzle, "error", Some(&e.to_string())),
+                },
+                Err(Error::Panicked) => {
+                    match format {
+                        OutputFormat::Human => eprintln!("Solution for day {day} panicked."),
+                        OutputFormat::Json => print_records(*puzzle, "panicked", None),
+                    }
+                    let panicked = PartStatus::Panicked {
+                        message: "solution panicked".into(),
+                        location: None,
+                    };
+                    timings.push(Timings::new(*puzzle, None, panicked.clone(), panicked));
+                }
+            }
         }
     });
 
+    if is_human {
+        print_summary(&timings);
+    }
+
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
-        println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
+        if is_human {
+            println!(
+                "\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            );
+        }
 
-        if is_release {
-            match readme_benchmarks::update(timings, total_millis) {
-                Ok(()) => println!("Successfully updated README with benchmarks."),
-                Err(_) => {
-                    eprintln!("Failed to update readme with benchmarks.");
-                }
+        if unreliable_days > 0 {
+            eprintln!(
+                "Not storing or comparing benchmarks, the timings of {unreliable_days} day(s) are unreliable."
+            );
+        } else if is_compare {
+            compare_history(&timings, format)?;
+        } else if is_release {
+            history::append(
+                history::HISTORY_PATH,
+                &history::entries_from_timings(&timings),
+            )?;
+            if is_human {
+                println!("Appended benchmarks to \"{}\".", history::HISTORY_PATH);
+            }
+
+            readme_benchmarks::update(year, &timings)?;
+            if is_human {
+                println!("Successfully updated README with benchmarks.");
             }
         }
     }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    Parser(String),
-    IO(io::Error),
+    outcome(&timings)
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
-    }
+/// Prints how many parts were solved, unsolved, panicked, failed or timed out.
+fn print_summary(timings: &[Timings]) {
+    println!("\n{ANSI_BOLD}Parts:{ANSI_RESET} {}", summary(timings));
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+fn summary(timings: &[Timings]) -> String {
+    let statuses: Vec<&PartStatus> = timings
+        .iter()
+        .flat_map(|t| [&t.part_1, &t.part_2])
+        .collect();
+    let count = |name: &str| statuses.iter().filter(|s| s.name() == name).count();
+
+    format!(
+        "{} solved, {} unsolved, {} panicked, {} failed, {} timed out",
+        count("solved"),
+        count("unsolved"),
+        count("panicked"),
+        count("failed"),
+        count("timed_out")
+    )
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
+/// Fails like a solution binary does, with [`template::Error::Failed`] if a part panicked, failed or
+/// timed out and with [`template::Error::NotSolved`] if a part returned no answer.
+fn outcome(timings: &[Timings]) -> Result<(), template::Error> {
+    let statuses = || timings.iter().flat_map(|t| [&t.part_1, &t.part_2]);
+
+    let failed = statuses()
+        .filter(|s| {
+            matches!(
+                s,
+                PartStatus::Panicked { .. } | PartStatus::Failed { .. } | PartStatus::TimedOut(_)
+            )
+        })
+        .count();
+    if failed > 0 {
+        return Err(template::Error::Failed(format!(
+            "{failed} part(s) panicked, failed or timed out."
+        )));
+    }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
-        }
+    let unsolved = statuses().filter(|s| **s == PartStatus::Unsolved).count();
+    if unsolved > 0 {
+        return Err(template::Error::NotSolved(format!(
+            "{unsolved} part(s) are not solved."
+        )));
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    Ok(())
+}
 
-        if is_release {
-            args.push("--release");
-        }
+/// Prints how `timings` compare to the stored history and fails on regressions.
+fn compare_history(timings: &[Timings], format: OutputFormat) -> Result<(), template::Error> {
+    let stored = history::load(history::HISTORY_PATH)?;
+
+    let comparisons = history::compare(&stored, &history::entries_from_timings(timings));
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    if format == OutputFormat::Human {
+        println!("\n{ANSI_BOLD}Compared to baseline:{ANSI_RESET}");
+        if comparisons.is_empty() {
+            println!("No baseline found in \"{}\".", history::HISTORY_PATH);
         }
+    }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    for comparison in &comparisons {
+        print_comparison(comparison, format);
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    let regressions = comparisons.iter().filter(|c| c.is_regression()).count();
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    if regressions > 0 {
+        return Err(template::Error::Failed(format!(
+            "{regressions} part(s) got slower than the baseline."
+        )));
+    }
 
-        let mut output = vec![];
+    Ok(())
+}
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+fn print_comparison(comparison: &Comparison, format: OutputFormat) {
+    let baseline = &comparison.baseline;
+    let status = if comparison.is_regression() {
+        "regressed"
+    } else {
+        "ok"
+    };
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    match format {
+        OutputFormat::Human => println!(
+            "Day {} {}: {:.1?} -> {:.1?} ({:+.1}%, baseline {}){}",
+            comparison.puzzle.day,
+            match comparison.part {
+                Part::Parse => "Parse".to_string(),
+                part => format!("Part {part}"),
+            },
+            baseline.stats.median,
+            comparison.current.median,
+            comparison.change() * 100.0,
+            baseline.commit,
+            if comparison.is_regression() {
+                format!(" {ANSI_BOLD}regressed{ANSI_RESET}")
+            } else {
+                String::new()
+            }
+        ),
+        OutputFormat::Json => {
+            let record = puzzle_record(comparison.puzzle);
+            let record = match comparison.part {
+                Part::Parse => record.string("step", "parse"),
+                Part::One => record.string("step", "part").number("part", 1),
+                Part::Two => record.string("step", "part").number("part", 2),
+            }
+            .string("status", status)
+            .string("baseline_commit", &baseline.commit)
+            .number("baseline_median_ns", baseline.stats.median.as_nanos())
+            .number("median_ns", comparison.current.median.as_nanos());
+            println!("{record}");
         }
+    }
+}
 
-        thread.join().unwrap();
-        cmd.wait()?;
+#[derive(Debug)]
+pub enum Error {
+    Panicked,
+    IO(io::Error),
+}
 
-        Ok(output)
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
     }
+}
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
-        };
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Panicked => f.write_str("solution panicked"),
+            Error::IO(e) => write!(f, "could not read input file: {e}"),
+        }
+    }
+}
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+/// Prints a JSON record with `status` for both parts of a puzzle that could not be run.
+fn print_records(puzzle: PuzzleId, status: &str, message: Option<&str>) {
+    for part in 1..=2 {
+        let record = part_record(puzzle, part, status);
+        match message {
+            Some(message) => println!("{}", record.string("message", message)),
+            None => println!("{record}"),
+        }
+    }
+}
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+/// The result of a day that ran on a worker thread, together with its captured output.
+type ParallelResult = (Result<Timings, Error>, String);
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+/// Solves days on worker threads and hands out the results in order.
+struct Parallel {
+    receiver: mpsc::Receiver<(usize, ParallelResult)>,
+    done: Vec<Option<ParallelResult>>,
+}
 
-                timings.total_nanos += nanos;
+impl Parallel {
+    /// Spawns `jobs` threads that take the days with a solution one after another.
+    fn spawn<'scope>(
+        scope: &'scope thread::Scope<'scope, '_>,
+        days: &'scope [(PuzzleId, Option<&Solution>)],
+        jobs: usize,
+    ) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let next = Arc::new(AtomicUsize::new(0));
+
+        for _ in 0..jobs {
+            let sender = sender.clone();
+            let next = Arc::clone(&next);
+
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some((_, solution)) = days.get(index) else {
+                    break;
+                };
+                let Some(solution) = solution else {
+                    continue;
+                };
+                let result = output::capture(|| run_solution(solution));
+                // the receiver is only gone if the main thread panicked.
+                if sender.send((index, result)).is_err() {
+                    break;
+                }
             });
+        }
 
-        timings
+        Self {
+            receiver,
+            done: days.iter().map(|_| None).collect(),
+        }
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+    /// Waits for the day at `index` to finish and returns its result.
+    fn take(&mut self, index: usize) -> ParallelResult {
+        loop {
+            if let Some(result) = self.done[index].take() {
+                return result;
+            }
+            let (done_index, result) = self
+                .receiver
+                .recv()
+                .expect("worker threads only stop after running every day");
+            self.done[done_index] = Some(result);
+        }
     }
+}
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+/// Reads the input of `solution` and runs it in-process.
+pub fn run_solution(solution: &Solution) -> Result<Timings, Error> {
+    let input = fs::read_to_string(solution.puzzle.input_path())?;
+    panic::catch_unwind(|| (solution.run)(&input)).map_err(|_| Error::Panicked)
+}
 
-        Some((str_timing, parsed_timing))
-    }
+/* -------------------------------------------------------------------------- */
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{outcome, summary};
+    use crate::template::{
+        readme_benchmarks::{PartStatus, Timings},
+        stats::Stats,
+        Error,
+    };
+    use crate::{day, year, PuzzleId};
+    use std::time::Duration;
+
+    #[test]
+    fn counts_panicked_solutions() {
+        let puzzle = PuzzleId::new(year!(2023), day!(13));
+        let panicked = PartStatus::Panicked {
+            message: "solution panicked".into(),
+            location: None,
+        };
+        let timings = [
+            Timings::new(puzzle, None, PartStatus::Unsolved, PartStatus::Unsolved),
+            Timings::new(puzzle, None, panicked.clone(), panicked),
+        ];
+        assert_eq!(
+            summary(&timings),
+            "0 solved, 2 unsolved, 2 panicked, 0 failed, 0 timed out"
+        );
     }
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn test_well_formed() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
-
-        #[test]
-        fn test_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
-
-        #[test]
-        fn test_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+    #[test]
+    fn fails_like_solution_binaries() {
+        let puzzle = PuzzleId::new(year!(2023), day!(13));
+        let solved = PartStatus::Solved {
+            answer: "42".into(),
+            stats: Stats::single(Duration::from_millis(1)),
+        };
+        let timed_out = PartStatus::TimedOut(Duration::from_secs(1));
+
+        let solved_day = Timings::new(puzzle, None, solved.clone(), solved.clone());
+        assert!(outcome(&[solved_day.clone()]).is_ok());
+
+        let unsolved_day = Timings::new(puzzle, None, solved, PartStatus::Unsolved);
+        assert!(matches!(
+            outcome(&[solved_day.clone(), unsolved_day.clone()]),
+            Err(Error::NotSolved(_))
+        ));
+
+        let timed_out_day = Timings::new(puzzle, None, PartStatus::Unsolved, timed_out);
+        assert!(matches!(
+            outcome(&[solved_day, unsolved_day, timed_out_day]),
+            Err(Error::Failed(_))
+        ));
     }
 }
+
+/* -------------------------------------------------------------------------- */
@@ -1,15 +1,30 @@
-use crate::template::aoc_cli;
-use crate::Day;
-use std::process;
-
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+use std::{fs, path::Path};
+
+use crate::template::{aoc_client::AocClient, Error};
+use crate::PuzzleId;
+
+pub fn handle(puzzle: PuzzleId) -> Result<(), Error> {
+    let client = AocClient::from_env()?;
+
+    let input_path = puzzle.input_path();
+    let puzzle_path = puzzle.puzzle_path();
+
+    let input = client.get_input(puzzle)?;
+    write_file(&input_path, &input).map_err(|e| Error::file(&input_path, e))?;
+    let description = client.get_puzzle(puzzle)?;
+    write_file(&puzzle_path, &description).map_err(|e| Error::file(&puzzle_path, e))?;
 
-    if let Err(e) = aoc_cli::download(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+
+    Ok(())
+}
+
+/// Writes `contents` to `path`, creating the folders of the year if needed.
+pub fn write_file(path: &str, contents: &str) -> std::io::Result<()> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
 }
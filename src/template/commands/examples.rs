@@ -0,0 +1,107 @@
+use std::{fs, io, path::Path};
+
+use crate::template::{
+    examples::{update_test, Puzzle},
+    Error,
+};
+use crate::PuzzleId;
+
+pub fn handle(puzzle_id: PuzzleId) -> Result<(), Error> {
+    let puzzle_path = puzzle_id.puzzle_path();
+    let module_path = puzzle_id.module_path();
+
+    let markdown = match fs::read_to_string(&puzzle_path) {
+        Ok(markdown) => markdown,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            return Err(Error::Usage(format!(
+                "puzzle \"{puzzle_path}\" not found. Run `cargo download {} --year {}` first.",
+                puzzle_id.day, puzzle_id.year
+            )));
+        }
+        Err(e) => return Err(Error::file(puzzle_path, e)),
+    };
+
+    let puzzle = Puzzle::parse(&markdown);
+
+    println!(
+        "Found {} code block(s) in \"{puzzle_path}\":",
+        puzzle.blocks.len()
+    );
+    for (i, block) in puzzle.blocks.iter().enumerate() {
+        let first_line = block.content.lines().next().unwrap_or_default();
+        println!(
+            "  {}. part {}, {} line(s): {first_line}",
+            i + 1,
+            block.part,
+            block.content.lines().count()
+        );
+    }
+
+    let Some(example_one) = puzzle.example(1) else {
+        return Err(Error::Failed("could not find an example input.".into()));
+    };
+
+    let example_two = puzzle
+        .example(2)
+        .filter(|block| block.content != example_one.content);
+
+    let files = match example_two {
+        Some(example_two) => vec![
+            (puzzle_id.example_path(Some(1)), example_one, Some(1)),
+            (puzzle_id.example_path(Some(2)), example_two, Some(2)),
+        ],
+        None => vec![(puzzle_id.example_path(None), example_one, None)],
+    };
+
+    for (path, block, _) in &files {
+        let is_empty = fs::read_to_string(path).map_or(true, |x| x.trim().is_empty());
+        if !is_empty {
+            println!("Skipped example file \"{path}\", it is not empty.");
+            continue;
+        }
+
+        fs::write(path, &block.content).map_err(|e| Error::file(path, e))?;
+        println!("Wrote example file \"{path}\"");
+    }
+
+    // the empty example file created by `scaffold` is replaced by the per-part files.
+    let shared_path = puzzle_id.example_path(None);
+    if files.len() > 1 && fs::read_to_string(&shared_path).is_ok_and(|x| x.is_empty()) {
+        let _ = fs::remove_file(&shared_path);
+    }
+
+    if !Path::new(&module_path).exists() {
+        println!("---");
+        println!(
+            "🎄 Type `cargo scaffold {} --year {}` to create a solution using these examples.",
+            puzzle_id.day, puzzle_id.year
+        );
+        return Ok(());
+    }
+
+    let mut source = fs::read_to_string(&module_path).map_err(|e| Error::file(&module_path, e))?;
+
+    for part in 1..=2 {
+        let Some(answer) = &puzzle.answers[usize::from(part - 1)] else {
+            println!("Could not find the expected answer for part {part}.");
+            continue;
+        };
+
+        let file_part = files.get(usize::from(part - 1)).and_then(|f| f.2);
+
+        match update_test(&source, part, answer, file_part) {
+            Some(updated) => {
+                source = updated;
+                println!("Expecting {answer} in test for part {part}");
+            }
+            None => println!("Could not find the test for part {part} in \"{module_path}\"."),
+        }
+    }
+
+    fs::write(&module_path, source).map_err(|e| Error::file(&module_path, e))?;
+
+    println!("---");
+    println!("🎄 Type `cargo test --bin {puzzle_id}` to run your solution against the examples.");
+
+    Ok(())
+}
@@ -1,16 +1,14 @@
-use std::process;
+use crate::template::{aoc_client::AocClient, commands::download::write_file, Error};
+use crate::PuzzleId;
 
-use crate::template::aoc_cli;
-use crate::Day;
+pub fn handle(puzzle_id: PuzzleId) -> Result<(), Error> {
+    let client = AocClient::from_env()?;
+    let puzzle = client.get_puzzle(puzzle_id)?;
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
-    }
+    println!("{puzzle}");
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
-    };
+    let puzzle_path = puzzle_id.puzzle_path();
+    write_file(&puzzle_path, &puzzle).map_err(|e| Error::file(&puzzle_path, e))?;
+
+    Ok(())
 }
@@ -1,18 +1,118 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::Path,
 };
 
-use crate::Day;
+use crate::template::{aoc_client, commands::download, examples::Puzzle, Error};
+use crate::PuzzleId;
 
-const MODULE_TEMPLATE: &str = r#"advent_of_code::solution!(DAY_NUMBER);
+/// Folder with user templates, e.g. `templates/grid.rs`. A template in this folder replaces the
+/// built-in template of the same name.
+const TEMPLATE_DIR: &str = "templates";
 
-pub fn part_one(input: &str) -> Option<u32> {
+/// The template that is used if no template is passed.
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+/// The answer type that is used if no answer type is passed.
+pub const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// The built-in templates by name. Templates may contain these placeholders:
+///  - `YEAR_NUMBER` and `DAY_NUMBER`, e.g. `2023` and `1`.
+///  - `PUZZLE_TITLE`, e.g. `Day 1: Trebuchet?!` if the puzzle was downloaded or `Day 1` otherwise.
+///  - `PUZZLE_URL`, e.g. `https://adventofcode.com/2023/day/1`.
+///  - `PUZZLE_DOC`, a module doc comment with the title, the url and a summary of the puzzle.
+///  - `ANSWER_TYPE`, e.g. `u32`.
+const TEMPLATES: [(&str, &str); 4] = [
+    (DEFAULT_TEMPLATE, MODULE_TEMPLATE),
+    ("parse", PARSE_TEMPLATE),
+    ("grid", GRID_TEMPLATE),
+    ("day25", DAY_25_TEMPLATE),
+];
+
+const MODULE_TEMPLATE: &str = r#"PUZZLE_DOC
+
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
+
+pub fn part_one(input: &str) -> Option<ANSWER_TYPE> {
+    None
+}
+
+pub fn part_two(input: &str) -> Option<ANSWER_TYPE> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+const PARSE_TEMPLATE: &str = r#"PUZZLE_DOC
+
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER, parse);
+
+pub fn parse(input: &str) -> Vec<&str> {
+    input.lines().collect()
+}
+
+pub fn part_one(lines: &[&str]) -> Option<ANSWER_TYPE> {
+    None
+}
+
+pub fn part_two(lines: &[&str]) -> Option<ANSWER_TYPE> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&parse(&advent_of_code::template::read_file("examples", PUZZLE)));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&parse(&advent_of_code::template::read_file("examples", PUZZLE)));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+const GRID_TEMPLATE: &str = r#"PUZZLE_DOC
+
+use advent_of_code::grid::Grid;
+
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER, parse);
+
+/// An empty input, e.g. the example before it is filled in, is an empty grid.
+pub fn parse(input: &str) -> Grid<char> {
+    if input.trim().is_empty() {
+        return Grid::new(0, 0, '.');
+    }
+    input.parse().expect("input should be a grid")
+}
+
+pub fn part_one(grid: &Grid<char>) -> Option<ANSWER_TYPE> {
     None
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(grid: &Grid<char>) -> Option<ANSWER_TYPE> {
     None
 }
 
@@ -22,73 +122,278 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&parse(&advent_of_code::template::read_file("examples", PUZZLE)));
         assert_eq!(result, None);
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&parse(&advent_of_code::template::read_file("examples", PUZZLE)));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+const DAY_25_TEMPLATE: &str = r#"PUZZLE_DOC
+
+advent_of_code::solution!(YEAR_NUMBER, DAY_NUMBER);
+
+pub fn part_one(input: &str) -> Option<ANSWER_TYPE> {
+    None
+}
+
+/// The last day has a single puzzle, its second star is awarded for all other stars.
+pub fn part_two(_input: &str) -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
 "#;
 
+/// Reads the template `name` from [`TEMPLATE_DIR`], falling back to the built-in template of that name.
+fn load_template(name: &str) -> Result<String, Error> {
+    let path = Path::new(TEMPLATE_DIR).join(format!("{name}.rs"));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => TEMPLATES
+            .iter()
+            .find(|(builtin, _)| *builtin == name)
+            .map(|(_, template)| (*template).to_string())
+            .ok_or_else(|| {
+                let builtins: Vec<&str> = TEMPLATES.iter().map(|(name, _)| *name).collect();
+                Error::Usage(format!(
+                    "unknown template \"{name}\". Expecting one of {} or a file \"{}\".",
+                    builtins.join(", "),
+                    path.display()
+                ))
+            }),
+        Err(e) => Err(Error::file(path.display().to_string(), e)),
+    }
+}
+
+/// Replaces the placeholders of `template`, see [`TEMPLATES`].
+/// `description` is the downloaded description of the puzzle, if any.
+fn render(
+    template: &str,
+    puzzle: PuzzleId,
+    description: Option<&Puzzle>,
+    answer_type: &str,
+) -> String {
+    let day = format!("Day {}", puzzle.day.into_inner());
+    let title = match description.and_then(|d| d.title.as_ref()) {
+        Some(title) => format!("{day}: {title}"),
+        None => day,
+    };
+    let url = aoc_client::puzzle_url(puzzle);
+
+    let mut doc = vec![format!("# {title}"), String::new(), format!("<{url}>")];
+    if let Some(summary) = description.and_then(|d| d.summary.as_ref()) {
+        doc.push(String::new());
+        doc.extend(wrap(summary, DOC_WIDTH));
+    }
+    let doc: Vec<String> = doc
+        .iter()
+        .map(|line| format!("//! {line}").trim_end().to_string())
+        .collect();
+
+    template
+        .replace("PUZZLE_DOC", &doc.join("\n"))
+        .replace("YEAR_NUMBER", &puzzle.year.to_string())
+        .replace("DAY_NUMBER", &puzzle.day.into_inner().to_string())
+        .replace("PUZZLE_TITLE", &title)
+        .replace("PUZZLE_URL", &url)
+        .replace("ANSWER_TYPE", answer_type)
+}
+
+/// The maximum width of the summary in the module doc comment.
+const DOC_WIDTH: usize = 96;
+
+/// Splits `text` into lines of at most `width` characters, unless a single word is longer.
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines: Vec<String> = vec![];
+
+    for word in text.split_whitespace() {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= width => {
+                line.push(' ');
+                line.push_str(word);
+            }
+            _ => lines.push(word.to_string()),
+        }
+    }
+
+    lines
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Creates the module, input and example files of `puzzle`. If `download` is set, the input and
+/// description are downloaded first, so that the module documents the puzzle.
+pub fn handle(
+    puzzle: PuzzleId,
+    template: &str,
+    answer_type: &str,
+    download: bool,
+) -> Result<(), Error> {
+    let template = load_template(template)?;
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
-    };
+    let input_path = puzzle.input_path();
+    let example_path = puzzle.example_path(None);
+    let module_path = puzzle.module_path();
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("DAY_NUMBER", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    if download {
+        if Path::new(&module_path).exists() {
+            return Err(Error::Usage(format!(
+                "module file \"{module_path}\" already exists."
+            )));
         }
+        download::handle(puzzle)?;
+        println!();
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    let mut file = safe_create_file(&module_path).map_err(|e| Error::file(&module_path, e))?;
+
+    let description = Puzzle::load(puzzle);
+    let module = render(&template, puzzle, description.as_ref(), answer_type);
+
+    file.write_all(module.as_bytes())
+        .map_err(|e| Error::file(&module_path, e))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    create_file(&input_path).map_err(|e| Error::file(&input_path, e))?;
+    println!("Created empty input file \"{}\"", &input_path);
+
+    create_file(&example_path).map_err(|e| Error::file(&example_path, e))?;
+    println!("Created empty example file \"{}\"", &example_path);
+
+    println!("---");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{load_template, render, TEMPLATES};
+    use crate::template::examples::Puzzle;
+    use crate::{day, year, Day, PuzzleId};
+    use std::{fs, path::Path, process::Command};
+
+    #[test]
+    fn renders_templates() {
+        let puzzle = PuzzleId::new(year!(2022), day!(5));
+
+        for (name, template) in TEMPLATES {
+            let module = render(template, puzzle, None, "i64");
+            assert!(
+                module.starts_with("//! # Day 5\n//!\n//! <https://adventofcode.com/2022/day/5>\n"),
+                "{name}"
+            );
+            assert!(module.contains("solution!(2022, 5"), "{name}");
+            assert!(module.contains("-> Option<i64>"), "{name}");
+            assert!(!module.contains("ANSWER_TYPE"), "{name}");
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    #[test]
+    fn documents_downloaded_puzzles() {
+        let puzzle = PuzzleId::new(year!(2022), day!(5));
+        let description = Puzzle {
+            title: Some("Supply Stacks".into()),
+            summary: Some(format!("{} end.", "word ".repeat(30))),
+            ..Puzzle::default()
+        };
+
+        let module = render(
+            "PUZZLE_DOC\n// PUZZLE_TITLE",
+            puzzle,
+            Some(&description),
+            "u32",
+        );
+        let lines: Vec<&str> = module.lines().collect();
+        assert_eq!(lines[0], "//! # Day 5: Supply Stacks");
+        assert_eq!(lines[3], "//!");
+        assert!(lines[4].starts_with("//! word word") && lines[4].len() <= 100);
+        assert!(lines[5].ends_with("word end."));
+        assert_eq!(lines[6], "// Day 5: Supply Stacks");
+    }
+
+    /// Scaffolds every template into a crate that depends on this one, with the empty example that
+    /// `scaffold` creates, and runs the tests of the scaffolded days.
+    #[test]
+    fn scaffolded_templates_pass_their_tests() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let dir = root.join("target").join("scaffold-test");
+        let _ = fs::remove_dir_all(dir.join("src"));
+        fs::create_dir_all(dir.join("src/bin")).unwrap();
+        fs::create_dir_all(dir.join("data/2023/examples")).unwrap();
+
+        let manifest = format!(
+            "[package]\nname = \"scaffold-test\"\nversion = \"0.0.0\"\nedition = \"2021\"\n\n\
+             [dependencies]\nadvent_of_code = {{ path = {:?} }}\n\n[workspace]\n",
+            root.display().to_string()
+        );
+        fs::write(dir.join("Cargo.toml"), manifest).unwrap();
+        let _ = fs::copy(root.join("Cargo.lock"), dir.join("Cargo.lock"));
+
+        for (day, (_, template)) in (1..).zip(TEMPLATES) {
+            let puzzle = PuzzleId::new(year!(2023), Day::new(day).unwrap());
+            fs::write(
+                dir.join(puzzle.module_path()),
+                render(template, puzzle, None, "u32"),
+            )
+            .unwrap();
+            fs::write(dir.join(puzzle.example_path(None)), "").unwrap();
         }
+
+        let output = Command::new(env!("CARGO"))
+            .args(["test", "--offline", "--bins"])
+            .current_dir(&dir)
+            .env("CARGO_TARGET_DIR", dir.join("target"))
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}{}",
+            String::from_utf8_lossy(&output.stdout),
+            String::from_utf8_lossy(&output.stderr)
+        );
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {}` to run your solution.", day);
+    #[test]
+    fn errors_on_unknown_templates() {
+        assert!(load_template("grid").is_ok());
+        assert!(load_template("unknown").is_err());
+    }
 }
+
+/* -------------------------------------------------------------------------- */
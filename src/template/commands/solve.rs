@@ -1,9 +1,23 @@
 use std::process::{Command, Stdio};
 
-use crate::Day;
-
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+use crate::template::{runner::OutputFormat, Error};
+use crate::PuzzleId;
+
+/// Runs the solution binary of `puzzle` with cargo. Fails with the exit code of the solution, e.g. if
+/// a part is not solved, see [`Error`].
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    time: bool,
+    submit_part: Option<u8>,
+    format: OutputFormat,
+) -> Result<(), Error> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
+
+    if format == OutputFormat::Json {
+        // keep cargo's own output out of stdout.
+        cmd_args.push("--quiet".to_string());
+    }
 
     if release {
         cmd_args.push("--release".to_string());
@@ -20,12 +34,24 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
-    let mut cmd = Command::new("cargo")
+    if format != OutputFormat::Human {
+        cmd_args.push("--format".to_string());
+        cmd_args.push(format.to_string());
+    }
+
+    let status = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|e| Error::spawn("cargo", e))?;
+
+    if !status.success() {
+        return Err(Error::Command {
+            command: format!("cargo {}", cmd_args.join(" ")),
+            code: status.code(),
+        });
+    }
 
-    cmd.wait().unwrap();
+    Ok(())
 }
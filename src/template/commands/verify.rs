@@ -0,0 +1,83 @@
+use crate::template::{
+    self, answers,
+    commands::all::{run_solution, Error},
+    readme_benchmarks::PartStatus,
+    runner::Solution,
+    ANSI_BOLD, ANSI_RESET,
+};
+use crate::{all_days, PuzzleId, Year};
+
+/// Runs all `solutions` of `year` that have stored answers on their real input and compares the results
+/// to the stored answers. Fails if any part does not produce its stored answer.
+pub fn handle(solutions: &[Solution], year: Year) -> Result<(), template::Error> {
+    let mut passed = 0;
+    let mut failed = 0;
+
+    for day in all_days() {
+        let puzzle = PuzzleId::new(year, day);
+
+        let Some(solution) = solutions.iter().find(|s| s.puzzle == puzzle) else {
+            continue;
+        };
+
+        let expected = [load_answer(puzzle, 1)?, load_answer(puzzle, 2)?];
+        if expected.iter().all(Option::is_none) {
+            continue;
+        }
+
+        if passed + failed > 0 {
+            println!();
+        }
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+
+        let statuses = match run_solution(solution) {
+            Ok(timings) => [timings.part_1, timings.part_2],
+            Err(Error::IO(e)) => {
+                eprintln!("Could not read input file for day {day}: {e}");
+                failed += expected.iter().flatten().count();
+                continue;
+            }
+            Err(Error::Panicked) => [1, 2].map(|_| PartStatus::Panicked {
+                message: "solution panicked".into(),
+                location: None,
+            }),
+        };
+
+        for (part, (expected, status)) in (1..).zip(expected.iter().zip(&statuses)) {
+            let Some(expected) = expected else {
+                continue;
+            };
+
+            match answers::verify(expected, status) {
+                Ok(()) => {
+                    passed += 1;
+                    println!("✔ Part {part} matches the stored answer.");
+                }
+                Err(mismatch) => {
+                    failed += 1;
+                    println!("✖ Part {part}: {mismatch}");
+                }
+            }
+        }
+    }
+
+    if passed + failed == 0 {
+        println!("No stored answers found for {year}.");
+        return Ok(());
+    }
+
+    println!("\n{ANSI_BOLD}Verified:{ANSI_RESET} {passed} passed, {failed} failed");
+
+    if failed > 0 {
+        return Err(template::Error::Failed(format!(
+            "{failed} part(s) did not produce their stored answer."
+        )));
+    }
+
+    Ok(())
+}
+
+fn load_answer(puzzle: PuzzleId, part: u8) -> Result<Option<String>, template::Error> {
+    answers::load(puzzle, part).map_err(|e| template::Error::file(puzzle.answer_path(part), e))
+}
@@ -0,0 +1,195 @@
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::{Error, ANSI_BOLD, ANSI_RESET};
+use crate::PuzzleId;
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Runs the example tests and then the solution of `puzzle` whenever its module, examples or input change.
+/// Changes are detected by polling the modification time and size of these files.
+/// Only returns if cargo could not be run.
+pub fn handle(puzzle: PuzzleId) -> Result<(), Error> {
+    let mut last_snapshot = None;
+
+    loop {
+        if last_snapshot.as_ref() != Some(&snapshot(puzzle)) {
+            if last_snapshot.is_some() {
+                println!();
+                println!("{ANSI_BOLD}Change detected{ANSI_RESET}");
+            }
+
+            run(puzzle)?;
+
+            println!("---");
+            println!(
+                "👀 Watching \"{}\" and the data of day {} for changes. Press Ctrl+C to stop.",
+                puzzle.module_path(),
+                puzzle.day
+            );
+
+            // take a new snapshot, running the tests might have taken a while.
+            last_snapshot = Some(snapshot(puzzle));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn run(puzzle: PuzzleId) -> Result<(), Error> {
+    let output = Command::new("cargo")
+        .args(["test", "--color", "always", "--bin", &puzzle.to_string()])
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| Error::spawn("cargo", e))?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    let Some(summary) = TestSummary::parse(&stdout) else {
+        // the solution did not compile, show the compiler output and wait for the next change.
+        eprint!("{}", String::from_utf8_lossy(&output.stderr));
+        println!("✖ Tests did not run.");
+        return Ok(());
+    };
+
+    if summary.failed.is_empty() {
+        println!("✔ Tests: {} passed", summary.passed);
+    } else {
+        print!("{stdout}");
+        println!(
+            "✖ Tests: {} passed, {} failed ({})",
+            summary.passed,
+            summary.failed.len(),
+            summary.failed.join(", ")
+        );
+    }
+
+    let input_path = puzzle.input_path();
+    if !Path::new(&input_path).exists() {
+        println!(
+            "No input at \"{input_path}\", run `cargo download {} --year {}` to solve the real input.",
+            puzzle.day, puzzle.year
+        );
+        return Ok(());
+    }
+
+    // a failing solution is reported by its own output, keep watching.
+    Command::new("cargo")
+        .args(["run", "--quiet", "--bin", &puzzle.to_string()])
+        .stdin(Stdio::null())
+        .status()
+        .map_err(|e| Error::spawn("cargo", e))?;
+
+    Ok(())
+}
+
+/// The modification time and size of every watched file that exists.
+fn snapshot(puzzle: PuzzleId) -> Vec<(PathBuf, SystemTime, u64)> {
+    let day = puzzle.day.to_string();
+    let example_path = puzzle.example_path(None);
+    let examples = Path::new(&example_path)
+        .parent()
+        .and_then(|folder| fs::read_dir(folder).ok())
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_stem()
+                .and_then(|stem| stem.to_str())
+                .is_some_and(|stem| stem == day || stem.starts_with(&format!("{day}-")))
+        });
+
+    let mut files: Vec<_> = [puzzle.module_path(), puzzle.input_path()]
+        .into_iter()
+        .map(PathBuf::from)
+        .chain(examples)
+        .filter_map(|path| {
+            let metadata = fs::metadata(&path).ok()?;
+            Some((path, metadata.modified().ok()?, metadata.len()))
+        })
+        .collect();
+
+    files.sort();
+    files
+}
+
+/// The outcome of a `cargo test` run.
+#[derive(Debug, PartialEq)]
+struct TestSummary {
+    passed: usize,
+    /// The names of the failed tests.
+    failed: Vec<String>,
+}
+
+impl TestSummary {
+    /// Reads the results of the individual tests from the output of `cargo test`.
+    /// Returns [`None`] if the output contains no test result, e.g. because the tests did not compile.
+    fn parse(output: &str) -> Option<Self> {
+        if !output.lines().any(|line| line.starts_with("test result:")) {
+            return None;
+        }
+
+        let mut summary = Self {
+            passed: 0,
+            failed: vec![],
+        };
+
+        for line in output.lines() {
+            let Some((name, result)) = line
+                .strip_prefix("test ")
+                .and_then(|line| line.split_once(" ... "))
+            else {
+                continue;
+            };
+
+            if result.contains("FAILED") {
+                summary.failed.push(name.to_string());
+            } else if result.contains("ok") {
+                summary.passed += 1;
+            }
+        }
+
+        Some(summary)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::TestSummary;
+
+    #[test]
+    fn parses_test_output() {
+        let output = [
+            "",
+            "running 3 tests",
+            "test tests::test_part_one ... ok",
+            "test tests::test_part_two ... \u{1b}[31mFAILED\u{1b}[0m",
+            "test tests::test_parse ... ok",
+            "",
+            "failures:",
+            "",
+            "test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out",
+        ]
+        .join("\n");
+
+        assert_eq!(
+            TestSummary::parse(&output),
+            Some(TestSummary {
+                passed: 2,
+                failed: vec!["tests::test_part_two".into()]
+            })
+        );
+        assert_eq!(TestSummary::parse("error[E0425]: cannot find value"), None);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
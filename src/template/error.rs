@@ -0,0 +1,204 @@
+use std::{fmt::Display, io, process::ExitCode};
+
+use crate::template::{aoc_client, commands::all, history, ledger, readme_benchmarks};
+
+/// An error of a template command or a solution binary.
+///
+/// Every kind of error exits the process with its own code, so that scripts can tell them apart:
+///
+/// | Code | Meaning                                                             |
+/// | ---- | ------------------------------------------------------------------- |
+/// | 1    | a check failed, e.g. a wrong stored answer, a panic or a regression |
+/// | 2    | invalid arguments, templates or a missing session cookie            |
+/// | 3    | a part is not solved                                                |
+/// | 4    | a required program, e.g. `cargo`, could not be started              |
+/// | 5    | a file could not be read or written                                 |
+/// | 6    | the request to adventofcode.com failed                              |
+///
+/// If a program run by a command fails, e.g. the solution run by `solve`, its exit code is passed on.
+#[derive(Debug)]
+pub enum Error {
+    /// A check failed, e.g. a part did not produce its stored answer.
+    Failed(String),
+    /// The arguments or a template are invalid.
+    Usage(String),
+    /// A part returned no answer. The part was already reported as unsolved.
+    NotSolved(String),
+    /// A program could not be started.
+    ToolMissing {
+        tool: String,
+        source: io::Error,
+    },
+    /// A program exited with a non-zero status. Its output was already shown.
+    Command {
+        command: String,
+        code: Option<i32>,
+    },
+    /// A file could not be read or written.
+    File {
+        path: String,
+        source: io::Error,
+    },
+    Client(aoc_client::Error),
+    Readme(readme_benchmarks::Error),
+    History(history::Error),
+    Ledger(ledger::Error),
+    Solution(all::Error),
+    IO(io::Error),
+}
+
+impl Error {
+    /// An error reading or writing the file at `path`.
+    pub fn file(path: impl Into<String>, source: io::Error) -> Self {
+        Error::File {
+            path: path.into(),
+            source,
+        }
+    }
+
+    /// An error starting the program `tool`, [`Error::ToolMissing`] if it is not installed.
+    pub fn spawn(tool: &str, source: io::Error) -> Self {
+        match source.kind() {
+            io::ErrorKind::NotFound => Error::ToolMissing {
+                tool: tool.into(),
+                source,
+            },
+            _ => Error::IO(source),
+        }
+    }
+
+    /// The exit code of the process for this error, see [`Error`].
+    pub fn exit_code(&self) -> ExitCode {
+        let code = match self {
+            Error::Failed(_) | Error::Solution(all::Error::Panicked) => 1,
+            Error::Usage(_) | Error::Client(aoc_client::Error::MissingSession) => 2,
+            Error::NotSolved(_) => 3,
+            Error::ToolMissing { .. } => 4,
+            Error::File { .. }
+            | Error::Readme(_)
+            | Error::History(_)
+            | Error::Ledger(_)
+            | Error::Solution(all::Error::IO(_))
+            | Error::Client(aoc_client::Error::IO(_))
+            | Error::IO(_) => 5,
+            Error::Client(_) => 6,
+            Error::Command { code, .. } => code.and_then(|x| u8::try_from(x).ok()).unwrap_or(1),
+        };
+        ExitCode::from(code)
+    }
+
+    /// Whether the error was already reported to the user when it happened.
+    pub fn is_reported(&self) -> bool {
+        matches!(self, Error::NotSolved(_) | Error::Command { .. })
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl From<aoc_client::Error> for Error {
+    fn from(e: aoc_client::Error) -> Self {
+        Error::Client(e)
+    }
+}
+
+impl From<readme_benchmarks::Error> for Error {
+    fn from(e: readme_benchmarks::Error) -> Self {
+        Error::Readme(e)
+    }
+}
+
+impl From<history::Error> for Error {
+    fn from(e: history::Error) -> Self {
+        Error::History(e)
+    }
+}
+
+impl From<ledger::Error> for Error {
+    fn from(e: ledger::Error) -> Self {
+        Error::Ledger(e)
+    }
+}
+
+impl From<all::Error> for Error {
+    fn from(e: all::Error) -> Self {
+        Error::Solution(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Failed(s) | Error::Usage(s) | Error::NotSolved(s) => f.write_str(s),
+            Error::ToolMissing { tool, source } => {
+                write!(f, "could not run `{tool}`: {source}. Is it installed?")
+            }
+            Error::Command {
+                command,
+                code: Some(code),
+            } => write!(f, "`{command}` exited with status {code}"),
+            Error::Command {
+                command,
+                code: None,
+            } => write!(f, "`{command}` was terminated"),
+            Error::File { path, source } => write!(f, "could not access \"{path}\": {source}"),
+            Error::Client(e) => e.fmt(f),
+            Error::Readme(e) => e.fmt(f),
+            Error::History(e) => e.fmt(f),
+            Error::Ledger(e) => e.fmt(f),
+            Error::Solution(e) => e.fmt(f),
+            Error::IO(e) => e.fmt(f),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Error;
+    use crate::template::{aoc_client, commands::all};
+    use std::{io, process::ExitCode};
+
+    #[test]
+    fn exit_codes() {
+        let io_error = || io::Error::from(io::ErrorKind::NotFound);
+
+        assert_eq!(Error::Failed(String::new()).exit_code(), ExitCode::from(1));
+        assert_eq!(Error::Usage(String::new()).exit_code(), ExitCode::from(2));
+        assert_eq!(
+            Error::NotSolved(String::new()).exit_code(),
+            ExitCode::from(3)
+        );
+        let missing = Error::ToolMissing {
+            tool: "cargo".into(),
+            source: io_error(),
+        };
+        assert_eq!(missing.exit_code(), ExitCode::from(4));
+        assert_eq!(
+            Error::file("a.txt", io_error()).exit_code(),
+            ExitCode::from(5)
+        );
+        assert_eq!(
+            Error::from(all::Error::IO(io_error())).exit_code(),
+            ExitCode::from(5)
+        );
+        assert_eq!(
+            Error::from(aoc_client::Error::MissingSession).exit_code(),
+            ExitCode::from(2)
+        );
+
+        let command = |code| Error::Command {
+            command: "cargo run".into(),
+            code,
+        };
+        assert_eq!(command(Some(3)).exit_code(), ExitCode::from(3));
+        assert_eq!(command(Some(-1)).exit_code(), ExitCode::from(1));
+        assert_eq!(command(None).exit_code(), ExitCode::from(1));
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,413 @@
+//! Module that finds example inputs and their expected answers in puzzle descriptions.
+//! Works on the markdown written by `download` and `read`, see [`crate::template::aoc_client::html::to_markdown`].
+use std::fs;
+
+use crate::PuzzleId;
+
+/// A fenced code block of a puzzle description.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CodeBlock {
+    /// The part whose description contains the block.
+    pub part: u8,
+    pub content: String,
+    /// The paragraph right before the block, e.g. "For example:".
+    pub context: String,
+}
+
+impl CodeBlock {
+    fn is_example(&self) -> bool {
+        self.context.to_ascii_lowercase().contains("example")
+    }
+}
+
+/// The title, code blocks and example answers of a puzzle description.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    /// The title of the puzzle without the day, e.g. "Trebuchet?!".
+    pub title: Option<String>,
+    /// The first paragraph of the description as plain text.
+    pub summary: Option<String>,
+    pub blocks: Vec<CodeBlock>,
+    /// The last emphasized inline code of each part, which is usually the answer for the example.
+    pub answers: [Option<String>; 2],
+}
+
+impl Puzzle {
+    /// Reads the description of `puzzle` written by `download`, [`None`] if it was not downloaded.
+    #[must_use]
+    pub fn load(puzzle: PuzzleId) -> Option<Self> {
+        let markdown = fs::read_to_string(puzzle.puzzle_path()).ok()?;
+        Some(Self::parse(&markdown))
+    }
+
+    #[must_use]
+    pub fn parse(markdown: &str) -> Self {
+        let mut puzzle = Self::default();
+        let mut part = 1;
+        let mut block: Option<String> = None;
+        let mut paragraph = String::new();
+        let mut in_paragraph = false;
+
+        for line in markdown.lines() {
+            if let Some(content) = &mut block {
+                if line == "```" {
+                    puzzle.blocks.push(CodeBlock {
+                        part,
+                        content: block.take().unwrap_or_default(),
+                        context: paragraph.trim().to_string(),
+                    });
+                    paragraph.clear();
+                    in_paragraph = false;
+                } else {
+                    content.push_str(line);
+                    content.push('\n');
+                }
+                continue;
+            }
+
+            if line.starts_with("```") {
+                block = Some(String::new());
+            } else if let Some(title) = parse_title(line) {
+                puzzle.title.get_or_insert(title);
+            } else if line.starts_with("## ") && line.contains("Part Two") {
+                part = 2;
+                paragraph.clear();
+                in_paragraph = false;
+            } else if line.trim().is_empty() {
+                // keep the last paragraph until the next one starts.
+                in_paragraph = false;
+            } else {
+                if !in_paragraph {
+                    paragraph.clear();
+                    in_paragraph = true;
+                }
+                paragraph.push_str(line);
+                paragraph.push('\n');
+
+                if puzzle.summary.is_none() && !line.starts_with('#') {
+                    puzzle.summary = Some(plain_text(line));
+                }
+
+                if let Some(answer) = last_answer(line) {
+                    puzzle.answers[usize::from(part - 1)] = Some(answer);
+                }
+            }
+        }
+
+        puzzle
+    }
+
+    /// Picks the example input of `part`:
+    ///  1. the first block of the part that is introduced as an example.
+    ///  2. for part one, the first block spanning more than one line, or the first block at all.
+    ///
+    /// Part two often reuses the example of part one, in which case `None` is returned.
+    #[must_use]
+    pub fn example(&self, part: u8) -> Option<&CodeBlock> {
+        let mut blocks = self.blocks.iter().filter(|b| b.part == part);
+
+        if let Some(block) = blocks.clone().find(|b| b.is_example()) {
+            return Some(block);
+        }
+
+        if part != 1 {
+            return None;
+        }
+
+        blocks
+            .clone()
+            .find(|b| b.content.lines().count() > 1)
+            .or_else(|| blocks.next())
+    }
+}
+
+/// Reads the title from a heading like "## --- Day 1: Trebuchet?! ---".
+fn parse_title(line: &str) -> Option<String> {
+    let heading = line.strip_prefix("## --- Day ")?.strip_suffix(" ---")?;
+    let (_, title) = heading.split_once(": ")?;
+    Some(title.trim().to_string())
+}
+
+/// Removes the markdown of a line, e.g. "*Sum* of [`x`](/x)" becomes "Sum of x".
+fn plain_text(line: &str) -> String {
+    let mut text = String::new();
+    let mut rest = line;
+
+    // keep the text of links and drop their target.
+    while let Some(start) = rest.find("](") {
+        let Some(end) = rest[start..].find(')') else {
+            break;
+        };
+        text.push_str(&rest[..start]);
+        rest = &rest[start + end + 1..];
+    }
+    text.push_str(rest);
+
+    text.chars()
+        .filter(|c| !matches!(c, '*' | '`' | '['))
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
+/// Finds the last inline code with emphasis in `line`, e.g. `42` in "produces `*42*`.".
+fn last_answer(line: &str) -> Option<String> {
+    let mut answer = None;
+    let mut rest = line;
+
+    while let Some(start) = rest.find("`*") {
+        let Some(end) = rest[start + 2..].find("*`") else {
+            break;
+        };
+        answer = Some(rest[start + 2..start + 2 + end].to_string());
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    answer
+}
+
+/// Sets the expected value of the test for `part` in the `source` of a solution to `answer`.
+/// The answer is written in the form of the current expected value: wrapped in `Ok(..)` or `Some(..)`, and as
+/// an integer or a string literal. A test that still expects `None` gets an integer if the answer is one.
+/// If `file_part` is passed, the test is switched to read the example file of that part.
+/// Returns `None` if the test could not be found.
+#[must_use]
+pub fn update_test(source: &str, part: u8, answer: &str, file_part: Option<u8>) -> Option<String> {
+    let name = if part == 1 { "one" } else { "two" };
+    let start = source.find(&format!("fn test_part_{name}()"))?;
+    let end = start + body_end(&source[start..])?;
+
+    let mut test = source[start..end].to_string();
+
+    let assert_start = test.find("assert_eq!(result, ")? + "assert_eq!(result, ".len();
+    let assert_end = test[assert_start..].find(");")? + assert_start;
+
+    let current = &test[assert_start..assert_end];
+    let wrapper = if current.starts_with("Ok(") {
+        "Ok"
+    } else {
+        "Some"
+    };
+    let expected = if current.contains('"') || !is_integer(answer) {
+        format!("{wrapper}({answer:?}.to_string())")
+    } else {
+        format!("{wrapper}({answer})")
+    };
+
+    test.replace_range(assert_start..assert_end, &expected);
+
+    if let Some(file_part) = file_part {
+        test = test.replace(
+            "read_file(\"examples\", PUZZLE)",
+            &format!("read_file_part(\"examples\", PUZZLE, {file_part})"),
+        );
+    }
+
+    Some(format!("{}{test}{}", &source[..start], &source[end..]))
+}
+
+/// Whether `answer` can be written as an integer literal of any size.
+fn is_integer(answer: &str) -> bool {
+    let digits = answer.strip_prefix('-').unwrap_or(answer);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+/// The position after the closing brace of the first block in `source`, e.g. the body of a function.
+/// Braces in string literals and line comments are skipped.
+fn body_end(source: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut chars = source.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '{' => depth += 1,
+            '}' => {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i + 1);
+                }
+            }
+            '"' => {
+                while let Some((_, c)) = chars.next() {
+                    match c {
+                        '\\' => {
+                            chars.next();
+                        }
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek().is_some_and(|(_, c)| *c == '/') => {
+                for (_, c) in chars.by_ref() {
+                    if c == '\n' {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    None
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{plain_text, update_test, Puzzle};
+
+    const PUZZLE: &str = "## --- Day 1: Trebuchet?! ---
+
+For example:
+
+```
+1abc2
+treb7uchet
+```
+
+In this example, the calibration values are `12` and `77`. Adding these together produces `*89*`.
+
+Consider your entire calibration document.
+
+## --- Part Two ---
+
+The sum is now `x`:
+
+```
+12
+```
+
+For example, suppose you have these lines:
+
+```
+two1nine
+7pqrstsixteen
+```
+
+Adding these together produces `*83*`.
+";
+
+    #[test]
+    fn parses_blocks_and_answers() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.title.as_deref(), Some("Trebuchet?!"));
+        assert_eq!(puzzle.summary.as_deref(), Some("For example:"));
+        assert_eq!(puzzle.blocks.len(), 3);
+        assert_eq!(puzzle.blocks[0].context, "For example:");
+        assert_eq!(puzzle.blocks[1].part, 2);
+        assert_eq!(puzzle.answers, [Some("89".into()), Some("83".into())]);
+    }
+
+    #[test]
+    fn picks_examples() {
+        let puzzle = Puzzle::parse(PUZZLE);
+        assert_eq!(puzzle.example(1).unwrap().content, "1abc2\ntreb7uchet\n");
+        assert_eq!(
+            puzzle.example(2).unwrap().content,
+            "two1nine\n7pqrstsixteen\n"
+        );
+    }
+
+    #[test]
+    fn reuses_example_of_part_one() {
+        let puzzle = Puzzle::parse(&PUZZLE.replace("For example, suppose", "Suppose"));
+        assert!(puzzle.example(2).is_none());
+    }
+
+    #[test]
+    fn updates_tests() {
+        let source = r#"#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+}
+"#;
+
+        let updated = update_test(source, 1, "89", Some(1)).unwrap();
+        let updated = update_test(&updated, 2, "abc", None).unwrap();
+
+        assert_eq!(
+            updated,
+            source
+                .replacen(
+                    "read_file(\"examples\", PUZZLE));\n        assert_eq!(result, None);",
+                    "read_file_part(\"examples\", PUZZLE, 1));\n        assert_eq!(result, Some(89));",
+                    1
+                )
+                .replacen(
+                    "assert_eq!(result, None);",
+                    "assert_eq!(result, Some(\"abc\".to_string()));",
+                    1
+                )
+        );
+    }
+
+    #[test]
+    fn keeps_ok_of_result_parts() {
+        let source = r#"
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Ok(8));
+    }
+"#;
+
+        let updated = update_test(source, 1, "42", None).unwrap();
+        assert_eq!(updated, source.replace("Ok(8)", "Ok(42)"));
+        let updated = update_test(source, 1, "abc", None).unwrap();
+        assert_eq!(updated, source.replace("Ok(8)", "Ok(\"abc\".to_string())"));
+    }
+
+    #[test]
+    fn keeps_literal_form_of_expected_values() {
+        let source = r#"
+    #[test]
+    fn test_part_one() {
+        // fn helpers in comments or strings {
+        fn example() -> String {
+            advent_of_code::template::read_file("examples", PUZZLE).replace("}", "")
+        }
+        let result = part_one(&example());
+        assert_eq!(result, Some("8".to_string()));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(8));
+    }
+"#;
+
+        let updated = update_test(source, 1, "42", None).unwrap();
+        assert_eq!(updated, source.replace("Some(\"8\"", "Some(\"42\""));
+        let updated = update_test(source, 2, "18446744073709551615", None).unwrap();
+        assert_eq!(
+            updated,
+            source.replace("Some(8)", "Some(18446744073709551615)")
+        );
+    }
+
+    #[test]
+    fn skips_missing_tests() {
+        assert!(update_test("fn main() {}", 1, "1", None).is_none());
+    }
+
+    #[test]
+    fn strips_markdown() {
+        assert_eq!(
+            plain_text("The *sum* of [all `x`](/2023/x) values."),
+            "The sum of all x values."
+        );
+    }
+}
@@ -0,0 +1,360 @@
+/// Module that stores benchmark results across runs and detects regressions against them.
+/// History is kept as a CSV file with one line per measured part.
+use std::{
+    cmp::Reverse,
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    process::Command,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::{readme_benchmarks::Timings, stats::Stats};
+use crate::PuzzleId;
+
+pub const HISTORY_PATH: &str = "data/benchmarks.csv";
+
+static HEADER: &str =
+    "timestamp,commit,year,day,part,samples,median_ns,mean_ns,stddev_ns,min_ns,p95_ns,max_ns";
+
+/// A part needs to be at least this much slower than its baseline to be flagged.
+const MIN_SLOWDOWN: f64 = 0.1;
+
+/// Welch's t statistic above which a slowdown is considered significant.
+const MIN_T_VALUE: f64 = 3.0;
+
+/// The number of most recent runs of a part that its baseline is picked from, see [`compare`].
+const BASELINE_RUNS: usize = 5;
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(s) => write!(f, "could not parse benchmark history: {s}"),
+            Error::IO(e) => write!(f, "could not access benchmark history: {e}"),
+        }
+    }
+}
+
+/// A timed step of a solution, as stored in the history.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    Parse,
+    One,
+    Two,
+}
+
+impl Display for Part {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Part::Parse => f.write_str("parse"),
+            Part::One => f.write_str("1"),
+            Part::Two => f.write_str("2"),
+        }
+    }
+}
+
+/// A single benchmark measurement.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub timestamp: u64,
+    pub commit: String,
+    pub puzzle: PuzzleId,
+    pub part: Part,
+    pub stats: Stats,
+}
+
+impl Entry {
+    fn to_csv(&self) -> String {
+        let s = &self.stats;
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{},{}",
+            self.timestamp,
+            self.commit,
+            self.puzzle.year,
+            self.puzzle.day,
+            self.part,
+            s.samples,
+            s.median.as_nanos(),
+            s.mean.as_nanos(),
+            s.stddev.as_nanos(),
+            s.min.as_nanos(),
+            s.p95.as_nanos(),
+            s.max.as_nanos()
+        )
+    }
+
+    fn from_csv(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+
+        let [timestamp, commit, year, day, part, samples, median, mean, stddev, min, p95, max] =
+            fields[..]
+        else {
+            return None;
+        };
+
+        let nanos = |s: &str| s.parse().ok().map(Duration::from_nanos);
+
+        Some(Self {
+            timestamp: timestamp.parse().ok()?,
+            commit: commit.to_string(),
+            puzzle: PuzzleId::new(year.parse().ok()?, day.parse().ok()?),
+            part: match part {
+                "parse" => Part::Parse,
+                "1" => Part::One,
+                "2" => Part::Two,
+                _ => return None,
+            },
+            stats: Stats {
+                samples: samples.parse().ok()?,
+                median: nanos(median)?,
+                mean: nanos(mean)?,
+                stddev: nanos(stddev)?,
+                min: nanos(min)?,
+                p95: nanos(p95)?,
+                max: nanos(max)?,
+            },
+        })
+    }
+}
+
+/// Converts the timings of a run into history entries for the current commit.
+#[must_use]
+pub fn entries_from_timings(timings: &[Timings]) -> Vec<Entry> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs());
+    let commit = get_commit();
+
+    timings
+        .iter()
+        .flat_map(|t| {
+            [
+                (Part::Parse, t.parse),
+                (Part::One, t.part_1.stats()),
+                (Part::Two, t.part_2.stats()),
+            ]
+            .into_iter()
+            .filter_map(move |(part, stats)| Some((t.puzzle, part, stats?)))
+        })
+        .map(|(puzzle, part, stats)| Entry {
+            timestamp,
+            commit: commit.clone(),
+            puzzle,
+            part,
+            stats,
+        })
+        .collect()
+}
+
+/// Reads all entries stored in the history file at `path`. A missing file is an empty history.
+pub fn load(path: &str) -> Result<Vec<Entry>, Error> {
+    if !Path::new(path).exists() {
+        return Ok(vec![]);
+    }
+
+    parse_history(&fs::read_to_string(path)?)
+}
+
+/// Appends `entries` to the history file at `path`, creating it if needed.
+pub fn append(path: &str, entries: &[Entry]) -> Result<(), Error> {
+    let exists = Path::new(path).exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    if !exists {
+        writeln!(file, "{HEADER}")?;
+    }
+
+    for entry in entries {
+        writeln!(file, "{}", entry.to_csv())?;
+    }
+
+    Ok(())
+}
+
+fn parse_history(content: &str) -> Result<Vec<Entry>, Error> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && *line != HEADER)
+        .map(|(i, line)| {
+            Entry::from_csv(line)
+                .ok_or_else(|| Error::Parser(format!("malformed entry on line {}", i + 1)))
+        })
+        .collect()
+}
+
+/// The result of comparing a measurement against the baseline of the same part, see [`compare`].
+#[derive(Clone, Debug)]
+pub struct Comparison {
+    pub puzzle: PuzzleId,
+    pub part: Part,
+    pub baseline: Entry,
+    pub current: Stats,
+}
+
+impl Comparison {
+    /// Relative change of the median, e.g. `0.1` if the part got 10% slower.
+    #[must_use]
+    pub fn change(&self) -> f64 {
+        let baseline = self.baseline.stats.median.as_nanos() as f64;
+        let current = self.current.median.as_nanos() as f64;
+        if baseline == 0.0 {
+            return 0.0;
+        }
+        (current - baseline) / baseline
+    }
+
+    /// Whether the part is significantly slower than the baseline: the median needs to have grown by more
+    /// than 10% and the difference of means needs to be significant according to Welch's t-test.
+    #[must_use]
+    pub fn is_regression(&self) -> bool {
+        self.change() > MIN_SLOWDOWN && welch_t(&self.baseline.stats, &self.current) > MIN_T_VALUE
+    }
+}
+
+/// Compares `current` entries to the baseline of the same puzzle and part in `history`: the entry with the
+/// median time of the last [`BASELINE_RUNS`] runs, the faster one for an even number of runs. A single
+/// regressed run that was stored therefore does not become the baseline. Parts without a baseline are skipped.
+#[must_use]
+pub fn compare(history: &[Entry], current: &[Entry]) -> Vec<Comparison> {
+    current
+        .iter()
+        .filter_map(|entry| {
+            let mut recent: Vec<&Entry> = history
+                .iter()
+                .filter(|h| h.puzzle == entry.puzzle && h.part == entry.part)
+                .collect();
+            recent.sort_by_key(|h| Reverse(h.timestamp));
+            recent.truncate(BASELINE_RUNS);
+            recent.sort_by_key(|h| h.stats.median);
+            let baseline = *recent.get(recent.len().checked_sub(1)? / 2)?;
+
+            Some(Comparison {
+                puzzle: entry.puzzle,
+                part: entry.part,
+                baseline: baseline.clone(),
+                current: entry.stats,
+            })
+        })
+        .collect()
+}
+
+fn welch_t(a: &Stats, b: &Stats) -> f64 {
+    let variance = |s: &Stats| (s.stddev.as_nanos() as f64).powi(2) / s.samples.max(1) as f64;
+    let diff = b.mean.as_nanos() as f64 - a.mean.as_nanos() as f64;
+    let error = (variance(a) + variance(b)).sqrt();
+
+    if error == 0.0 {
+        return if diff > 0.0 { f64::INFINITY } else { 0.0 };
+    }
+
+    diff / error
+}
+
+fn get_commit() -> String {
+    Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{compare, parse_history, Entry, Part, HEADER};
+    use crate::{day, template::stats::Stats, year, PuzzleId};
+    use std::time::Duration;
+
+    fn entry(timestamp: u64, part: Part, median: u64, stddev: u64) -> Entry {
+        let mut stats = Stats::single(Duration::from_micros(median));
+        stats.stddev = Duration::from_micros(stddev);
+        stats.samples = 100;
+
+        Entry {
+            timestamp,
+            commit: "abc1234".into(),
+            puzzle: PuzzleId::new(year!(2023), day!(5)),
+            part,
+            stats,
+        }
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entries = [entry(1, Part::Parse, 10, 1), entry(1, Part::Two, 20, 2)];
+        let content = [HEADER.to_string(), entries[0].to_csv(), entries[1].to_csv()].join("\n");
+        assert_eq!(parse_history(&content).unwrap(), entries);
+    }
+
+    #[test]
+    fn errors_on_malformed_entries() {
+        assert!(parse_history("1,abc,2023,05,3,1,1,1,1,1,1,1").is_err());
+        assert!(parse_history("1,abc,05,1,1,1,1,1,1,1,1").is_err());
+        assert!(parse_history("1,abc,2023,05,1").is_err());
+    }
+
+    #[test]
+    fn compares_against_latest_baseline() {
+        let history = [entry(1, Part::One, 100, 1), entry(2, Part::One, 10, 1)];
+        let comparisons = compare(&history, &[entry(3, Part::One, 12, 1)]);
+        assert_eq!(comparisons.len(), 1);
+        assert_eq!(comparisons[0].baseline.timestamp, 2);
+        assert!(comparisons[0].is_regression());
+    }
+
+    #[test]
+    fn compares_against_median_of_recent_runs() {
+        let history = [
+            entry(1, Part::One, 1, 0),
+            entry(2, Part::One, 10, 1),
+            entry(3, Part::One, 11, 1),
+            entry(4, Part::One, 10, 1),
+            entry(5, Part::One, 10, 1),
+            entry(6, Part::One, 20, 1),
+        ];
+        let comparisons = compare(&history, &[entry(7, Part::One, 20, 1)]);
+        assert_eq!(
+            comparisons[0].baseline.stats.median,
+            Duration::from_micros(10)
+        );
+        assert!(comparisons[0].is_regression());
+
+        let comparisons = compare(&history[..2], &[entry(7, Part::One, 20, 1)]);
+        assert_eq!(comparisons[0].baseline.timestamp, 1);
+    }
+
+    #[test]
+    fn ignores_noise() {
+        let history = [entry(1, Part::One, 100, 50)];
+        let comparisons = compare(&history, &[entry(2, Part::One, 110, 50)]);
+        assert!(!comparisons[0].is_regression());
+    }
+
+    #[test]
+    fn ignores_small_slowdowns() {
+        let history = [entry(1, Part::One, 100, 0)];
+        let comparisons = compare(&history, &[entry(2, Part::One, 109, 0)]);
+        assert!(!comparisons[0].is_regression());
+    }
+
+    #[test]
+    fn skips_parts_without_baseline() {
+        let history = [entry(1, Part::One, 100, 0)];
+        assert!(compare(&history, &[entry(2, Part::Two, 100, 0)]).is_empty());
+    }
+}
@@ -0,0 +1,108 @@
+/// Minimal JSON serialization used for machine-readable output.
+use std::fmt::{Display, Write};
+
+/// A JSON object that serializes its fields in insertion order.
+#[derive(Clone, Debug, Default)]
+pub struct Object {
+    fields: Vec<(String, String)>,
+}
+
+impl Object {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    #[must_use]
+    pub fn string(self, key: &str, value: &str) -> Self {
+        self.raw(key, escape(value))
+    }
+
+    /// Adds a numeric field. `value` is expected to display as a valid JSON number.
+    #[must_use]
+    pub fn number(self, key: &str, value: impl Display) -> Self {
+        self.raw(key, value.to_string())
+    }
+
+    #[must_use]
+    pub fn null(self, key: &str) -> Self {
+        self.raw(key, "null".into())
+    }
+
+    #[must_use]
+    pub fn object(self, key: &str, value: &Object) -> Self {
+        self.raw(key, value.to_string())
+    }
+
+    fn raw(mut self, key: &str, value: String) -> Self {
+        self.fields.push((escape(key), value));
+        self
+    }
+}
+
+impl Display for Object {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_char('{')?;
+        for (i, (key, value)) in self.fields.iter().enumerate() {
+            if i > 0 {
+                f.write_char(',')?;
+            }
+            write!(f, "{key}:{value}")?;
+        }
+        f.write_char('}')
+    }
+}
+
+/// Encodes `s` as a quoted JSON string.
+#[must_use]
+pub fn escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{escape, Object};
+
+    #[test]
+    fn escapes_strings() {
+        assert_eq!(escape("plain"), r#""plain""#);
+        assert_eq!(escape("a \"b\" \\ c"), r#""a \"b\" \\ c""#);
+        assert_eq!(escape("line\nbreak\t\u{1}"), r#""line\nbreak\t\u0001""#);
+    }
+
+    #[test]
+    fn serializes_objects() {
+        let inner = Object::new().number("ns", 42);
+        let object = Object::new()
+            .number("day", 1)
+            .string("answer", "4\n2")
+            .null("missing")
+            .object("stats", &inner);
+
+        assert_eq!(
+            object.to_string(),
+            r#"{"day":1,"answer":"4\n2","missing":null,"stats":{"ns":42}}"#
+        );
+    }
+
+    #[test]
+    fn serializes_empty_object() {
+        assert_eq!(Object::new().to_string(), "{}");
+    }
+}
@@ -0,0 +1,338 @@
+/// Module that keeps track of submitted answers and their verdicts.
+/// The ledger is used to refuse submissions that are known to be wrong before they hit the rate limit.
+/// It is kept as a CSV file with one line per submission.
+use std::{
+    fmt::Display,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::template::aoc_client::Verdict;
+use crate::{PuzzleId, Year};
+
+pub const LEDGER_PATH: &str = "data/submissions.csv";
+
+static HEADER: &str = "timestamp,year,day,part,verdict,answer";
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(String),
+    IO(io::Error),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(s) => write!(f, "could not parse submission ledger: {s}"),
+            Error::IO(e) => write!(f, "could not access submission ledger: {e}"),
+        }
+    }
+}
+
+/// A submitted answer and the verdict it received.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub timestamp: u64,
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+}
+
+impl Submission {
+    #[must_use]
+    pub fn new(puzzle: PuzzleId, part: u8, answer: &str, verdict: Verdict) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+
+        Self {
+            timestamp,
+            puzzle,
+            part,
+            answer: answer.to_string(),
+            verdict,
+        }
+    }
+
+    fn to_csv(&self) -> String {
+        let verdict = self.verdict.name();
+
+        // the answer goes last so it may contain commas.
+        format!(
+            "{},{},{},{},{verdict},{}",
+            self.timestamp,
+            self.puzzle.year,
+            self.puzzle.day,
+            self.part,
+            escape(&self.answer)
+        )
+    }
+
+    fn from_csv(line: &str) -> Option<Self> {
+        let fields: Vec<&str> = line.splitn(6, ',').collect();
+
+        let [timestamp, year, day, part, verdict, answer] = fields[..] else {
+            return None;
+        };
+
+        Some(Self {
+            timestamp: timestamp.trim().parse().ok()?,
+            puzzle: PuzzleId::new(year.parse::<Year>().ok()?, day.trim().parse().ok()?),
+            part: part.trim().parse().ok().filter(|p| (1..=2).contains(p))?,
+            answer: unescape(answer),
+            verdict: match verdict.trim() {
+                "correct" => Verdict::Correct,
+                "incorrect" => Verdict::Incorrect,
+                "too_high" => Verdict::TooHigh,
+                "too_low" => Verdict::TooLow,
+                "rate_limited" => Verdict::RateLimited(None),
+                "wrong_level" => Verdict::WrongLevel,
+                "unknown" => Verdict::Unknown(String::new()),
+                _ => return None,
+            },
+        })
+    }
+}
+
+/// Why a submission was refused without contacting the website.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Rejection {
+    /// The part is already solved, contains the accepted answer.
+    AlreadySolved(String),
+    /// The exact answer was submitted before and was wrong.
+    Duplicate(Verdict),
+    /// The answer is at least as high as an answer that was too high.
+    AboveBound(i128),
+    /// The answer is at most as low as an answer that was too low.
+    BelowBound(i128),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::AlreadySolved(answer) => {
+                write!(f, "This part was already solved with answer \"{answer}\".")
+            }
+            Rejection::Duplicate(verdict) => {
+                write!(f, "This answer was already submitted. {verdict}")
+            }
+            Rejection::AboveBound(bound) => write!(
+                f,
+                "This answer can't be right, {bound} was already too high."
+            ),
+            Rejection::BelowBound(bound) => {
+                write!(
+                    f,
+                    "This answer can't be right, {bound} was already too low."
+                )
+            }
+        }
+    }
+}
+
+/// Checks `answer` for a part against all previous `submissions`.
+/// Only submissions with a definite verdict are considered, e.g. rate limited answers may be submitted again.
+pub fn check(
+    submissions: &[Submission],
+    puzzle: PuzzleId,
+    part: u8,
+    answer: &str,
+) -> Result<(), Rejection> {
+    let previous: Vec<&Submission> = submissions
+        .iter()
+        .filter(|s| s.puzzle == puzzle && s.part == part)
+        .collect();
+
+    if let Some(solved) = previous.iter().find(|s| s.verdict == Verdict::Correct) {
+        return Err(Rejection::AlreadySolved(solved.answer.clone()));
+    }
+
+    if let Some(duplicate) = previous.iter().find(|s| {
+        s.answer == answer
+            && matches!(
+                s.verdict,
+                Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+            )
+    }) {
+        return Err(Rejection::Duplicate(duplicate.verdict.clone()));
+    }
+
+    let Ok(value) = answer.trim().parse::<i128>() else {
+        return Ok(());
+    };
+
+    let bound = |verdict: Verdict| {
+        previous
+            .iter()
+            .filter(move |s| s.verdict == verdict)
+            .filter_map(|s| s.answer.trim().parse::<i128>().ok())
+    };
+
+    if let Some(upper) = bound(Verdict::TooHigh).min().filter(|&x| value >= x) {
+        return Err(Rejection::AboveBound(upper));
+    }
+
+    if let Some(lower) = bound(Verdict::TooLow).max().filter(|&x| value <= x) {
+        return Err(Rejection::BelowBound(lower));
+    }
+
+    Ok(())
+}
+
+/// Reads all submissions stored in the ledger at `path`. A missing file is an empty ledger.
+pub fn load(path: &str) -> Result<Vec<Submission>, Error> {
+    if !Path::new(path).exists() {
+        return Ok(vec![]);
+    }
+
+    parse_ledger(&fs::read_to_string(path)?)
+}
+
+/// Appends `submission` to the ledger at `path`, creating it if needed.
+pub fn append(path: &str, submission: &Submission) -> Result<(), Error> {
+    let exists = Path::new(path).exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+
+    if !exists {
+        writeln!(file, "{HEADER}")?;
+    }
+
+    writeln!(file, "{}", submission.to_csv())?;
+    Ok(())
+}
+
+fn parse_ledger(content: &str) -> Result<Vec<Submission>, Error> {
+    content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty() && *line != HEADER)
+        .map(|(i, line)| {
+            Submission::from_csv(line)
+                .ok_or_else(|| Error::Parser(format!("malformed submission on line {}", i + 1)))
+        })
+        .collect()
+}
+
+/// Escapes line breaks so multi-line answers fit on a single line.
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            (c, _) => out.push(c),
+        }
+    }
+
+    out
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check, parse_ledger, Rejection, Submission, HEADER};
+    use crate::{day, template::aoc_client::Verdict, year, PuzzleId};
+
+    const PUZZLE: PuzzleId = PuzzleId::new(year!(2023), day!(3));
+
+    fn submission(part: u8, answer: &str, verdict: Verdict) -> Submission {
+        Submission {
+            timestamp: 1,
+            puzzle: PUZZLE,
+            part,
+            answer: answer.into(),
+            verdict,
+        }
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let submissions = [
+            submission(1, "42", Verdict::TooLow),
+            submission(2, "a,b\n\\n", Verdict::Incorrect),
+        ];
+        let content = [
+            HEADER.to_string(),
+            submissions[0].to_csv(),
+            submissions[1].to_csv(),
+        ]
+        .join("\n");
+        assert_eq!(parse_ledger(&content).unwrap(), submissions);
+    }
+
+    #[test]
+    fn errors_on_malformed_submissions() {
+        assert!(parse_ledger("1,2023,03,3,correct,42").is_err());
+        assert!(parse_ledger("1,2023,03,1,maybe,42").is_err());
+        assert!(parse_ledger("1,2014,03,1,correct,42").is_err());
+        assert!(parse_ledger("1,2023,03,1").is_err());
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let submissions = [submission(1, "42", Verdict::Correct)];
+        assert_eq!(
+            check(&submissions, PUZZLE, 1, "43"),
+            Err(Rejection::AlreadySolved("42".into()))
+        );
+        assert_eq!(check(&submissions, PUZZLE, 2, "43"), Ok(()));
+        assert_eq!(
+            check(&submissions, PuzzleId::new(year!(2023), day!(4)), 1, "43"),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn refuses_duplicates() {
+        let submissions = [
+            submission(1, "abc", Verdict::Incorrect),
+            submission(1, "def", Verdict::RateLimited(None)),
+        ];
+        assert_eq!(
+            check(&submissions, PUZZLE, 1, "abc"),
+            Err(Rejection::Duplicate(Verdict::Incorrect))
+        );
+        assert_eq!(check(&submissions, PUZZLE, 1, "def"), Ok(()));
+    }
+
+    #[test]
+    fn refuses_answers_out_of_bounds() {
+        let submissions = [
+            submission(1, "100", Verdict::TooHigh),
+            submission(1, "80", Verdict::TooHigh),
+            submission(1, "10", Verdict::TooLow),
+            submission(1, "20", Verdict::TooLow),
+        ];
+        assert_eq!(
+            check(&submissions, PUZZLE, 1, "90"),
+            Err(Rejection::AboveBound(80))
+        );
+        assert_eq!(
+            check(&submissions, PUZZLE, 1, "15"),
+            Err(Rejection::BelowBound(20))
+        );
+        assert_eq!(check(&submissions, PUZZLE, 1, "21"), Ok(()));
+        assert_eq!(check(&submissions, PUZZLE, 1, "79"), Ok(()));
+        assert_eq!(check(&submissions, PUZZLE, 1, "x"), Ok(()));
+    }
+}
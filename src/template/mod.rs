@@ -1,10 +1,20 @@
-use crate::Day;
+use crate::PuzzleId;
 use std::{env, fs};
 
-pub mod aoc_cli;
+pub mod answers;
+pub mod aoc_client;
 pub mod commands;
+mod error;
+pub mod examples;
+pub mod history;
+pub mod json;
+pub mod ledger;
+pub mod output;
 pub mod readme_benchmarks;
 pub mod runner;
+pub mod stats;
+
+pub use error::Error;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
@@ -12,37 +22,122 @@ pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
+        .join(puzzle.year.to_string())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// If a parse function is passed as third argument, e.g. `solution!(2023, 5, parse)`, the input is parsed
+/// once and a reference to the parsed value is passed to both parts. A parse function that returns a
+/// [`Result`] is marked with a `?`, e.g. `solution!(2023, 5, parse?)`. If parsing fails or panics, the
+/// parts do not run and are reported with the status of the parse step.
+///
+/// A timeout in seconds for each part, overriding the default from `AOC_TIMEOUT`, can be passed last,
+/// e.g. `solution!(2023, 5, parse, timeout = 300)`.
+///
+/// The macro also exposes the solution as `SOLUTION`, which is used to link all solutions into the main binary.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@solution $year:expr, $day:expr) => {
+        /// The year of the puzzle.
+        const YEAR: advent_of_code::Year = advent_of_code::year!($year);
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
+        /// The puzzle this solution solves.
+        const PUZZLE: advent_of_code::PuzzleId = advent_of_code::PuzzleId::new(YEAR, DAY);
+
+        #[doc(hidden)]
+        pub const SOLUTION: advent_of_code::template::runner::Solution =
+            advent_of_code::template::runner::Solution { puzzle: PUZZLE, run: __run };
+
+        fn main() -> std::process::ExitCode {
+            advent_of_code::template::runner::run_main(&SOLUTION)
+        }
+    };
+    ($year:literal, $day:literal $(, timeout = $timeout:literal)?) => {
+        $crate::solution!(@solution $year, $day);
+
+        #[doc(hidden)]
+        pub fn __run(input: &str) -> advent_of_code::template::readme_benchmarks::Timings {
+            use advent_of_code::template::runner::*;
+            let (part_1, part_2) = match part_timeout(None $(.or(Some($timeout)))?) {
+                Some(timeout) => {
+                    // leaked so that a part that timed out can keep running in the background.
+                    let input: &'static str = Box::leak(input.into());
+                    (
+                        run_part_with_timeout(part_one, input, PUZZLE, 1, timeout),
+                        run_part_with_timeout(part_two, input, PUZZLE, 2, timeout),
+                    )
+                }
+                None => (run_part(part_one, input, PUZZLE, 1), run_part(part_two, input, PUZZLE, 2)),
+            };
+            advent_of_code::template::readme_benchmarks::Timings::new(PUZZLE, None, part_1, part_2)
+        }
+    };
+    ($year:literal, $day:literal, $parse:ident? $(, timeout = $timeout:literal)?) => {
+        $crate::solution!(@solution $year, $day);
 
-        fn main() {
+        #[doc(hidden)]
+        pub fn __run(input: &str) -> advent_of_code::template::readme_benchmarks::Timings {
             use advent_of_code::template::runner::*;
-            let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+            let timeout = part_timeout(None $(.or(Some($timeout)))?);
+            $crate::solution!(@parsed run_try_parse, $parse, input, timeout)
+        }
+    };
+    ($year:literal, $day:literal, $parse:ident $(, timeout = $timeout:literal)?) => {
+        $crate::solution!(@solution $year, $day);
+
+        #[doc(hidden)]
+        pub fn __run(input: &str) -> advent_of_code::template::readme_benchmarks::Timings {
+            use advent_of_code::template::runner::*;
+            let timeout = part_timeout(None $(.or(Some($timeout)))?);
+            $crate::solution!(@parsed run_parse, $parse, input, timeout)
+        }
+    };
+    (@parsed $run_parse:ident, $parse:ident, $input:ident, $timeout:ident) => {
+        match $timeout {
+            Some(timeout) => {
+                // leaked so that a part that timed out can keep running in the background, also if the
+                // parsed value borrows from the input.
+                let input: &'static str = Box::leak($input.into());
+                let (parsed, parse) = $crate::solution!(@parse $run_parse, $parse, input);
+                let parsed: &'static _ = Box::leak(Box::new(parsed));
+                let part_1 = run_part_with_timeout(part_one, parsed, PUZZLE, 1, timeout);
+                let part_2 = run_part_with_timeout(part_two, parsed, PUZZLE, 2, timeout);
+                advent_of_code::template::readme_benchmarks::Timings::new(PUZZLE, Some(parse), part_1, part_2)
+            }
+            None => {
+                let (parsed, parse) = $crate::solution!(@parse $run_parse, $parse, $input);
+                let part_1 = run_part(part_one, &parsed, PUZZLE, 1);
+                let part_2 = run_part(part_two, &parsed, PUZZLE, 2);
+                advent_of_code::template::readme_benchmarks::Timings::new(PUZZLE, Some(parse), part_1, part_2)
+            }
+        }
+    };
+    // parses `$input`, returns the status of the parse step for both parts if it failed.
+    (@parse $run_parse:ident, $parse:ident, $input:expr) => {
+        match $run_parse($parse, $input, PUZZLE) {
+            Ok(parsed) => parsed,
+            Err(status) => {
+                return advent_of_code::template::readme_benchmarks::Timings::new(PUZZLE, None, (*status).clone(), *status);
+            }
         }
     };
 }
@@ -0,0 +1,65 @@
+/// Module that routes the output of the runner. By default output goes to stdout, but it can be
+/// captured per thread so that `all` can run several days at once and still print each day as a group.
+use std::cell::RefCell;
+use std::fmt::{self, Write};
+
+thread_local! {
+    /// The output captured on this thread, [`None`] if output goes to stdout.
+    static CAPTURED: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Writes to the capture of the current thread if one is active, to stdout otherwise.
+/// Use the [`out`] and [`outln`] macros instead of calling this directly.
+pub fn write(args: fmt::Arguments) {
+    CAPTURED.with_borrow_mut(|captured| match captured {
+        Some(captured) => {
+            let _ = captured.write_fmt(args);
+        }
+        None => print!("{args}"),
+    });
+}
+
+/// Runs `func` and returns its result together with the output the runner wrote on this thread.
+pub fn capture<T>(func: impl FnOnce() -> T) -> (T, String) {
+    let previous = CAPTURED.replace(Some(String::new()));
+    let result = func();
+    let captured = CAPTURED.replace(previous).unwrap_or_default();
+    (result, captured)
+}
+
+/// Like [`print!`], but respects [`capture`].
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::template::output::write(format_args!($($arg)*))
+    };
+}
+
+/// Like [`println!`], but respects [`capture`].
+macro_rules! outln {
+    ($($arg:tt)*) => {
+        $crate::template::output::write(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use {out, outln};
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::capture;
+
+    #[test]
+    fn captures_output() {
+        let (result, output) = capture(|| {
+            out!("Part {}: ", 1);
+            let (_, inner) = capture(|| outln!("nested"));
+            outln!("{}", 42);
+            inner
+        });
+        assert_eq!(result, "nested\n");
+        assert_eq!(output, "Part 1: 42\n");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
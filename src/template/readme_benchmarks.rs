@@ -1,8 +1,9 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{cmp::Reverse, collections::BTreeMap, fmt::Display, fs, io, time::Duration};
 
-use crate::Day;
+use crate::template::{examples::Puzzle, stats::Stats};
+use crate::{PuzzleId, Year};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -18,22 +19,97 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(s) => write!(f, "could not update readme with benchmarks: {s}"),
+            Error::IO(e) => write!(f, "could not access readme: {e}"),
+        }
+    }
+}
+
+/// The outcome of running one part of a solution.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PartStatus {
+    /// The part returned an answer.
+    Solved { answer: String, stats: Stats },
+    /// The part returned [`None`].
+    Unsolved,
+    /// The part panicked with a message at a location, e.g. `src/bin/2023-10.rs:12:5`.
+    Panicked {
+        message: String,
+        location: Option<String>,
+    },
+    /// The part returned an error.
+    Failed { message: String },
+    /// The part did not finish within the timeout.
+    TimedOut(Duration),
+}
+
+impl PartStatus {
+    /// The timing of the part, only available if it was solved.
+    #[must_use]
+    pub fn stats(&self) -> Option<Stats> {
+        match self {
+            Self::Solved { stats, .. } => Some(*stats),
+            _ => None,
+        }
+    }
+
+    /// The status as used in JSON records.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Solved { .. } => "solved",
+            Self::Unsolved => "unsolved",
+            Self::Panicked { .. } => "panicked",
+            Self::Failed { .. } => "failed",
+            Self::TimedOut(_) => "timed_out",
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct Timings {
-    pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub puzzle: PuzzleId,
+    pub parse: Option<Stats>,
+    pub part_1: PartStatus,
+    pub part_2: PartStatus,
     pub total_nanos: f64,
 }
 
+impl Timings {
+    #[must_use]
+    pub fn new(
+        puzzle: PuzzleId,
+        parse: Option<Stats>,
+        part_1: PartStatus,
+        part_2: PartStatus,
+    ) -> Self {
+        let total_nanos = [parse, part_1.stats(), part_2.stats()]
+            .iter()
+            .flatten()
+            .map(|stats| stats.median.as_nanos() as f64)
+            .sum();
+
+        Self {
+            puzzle,
+            parse,
+            part_1,
+            part_2,
+            total_nanos,
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./{}", puzzle.module_path())
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -58,75 +134,240 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// The title of a puzzle for the benchmark table, e.g. `Day 1: Trebuchet?!`. Read from the heading of the
+/// module doc comment written by `scaffold`, or from the puzzle description if it was downloaded.
+fn puzzle_title(puzzle: PuzzleId) -> Option<String> {
+    let from_module = fs::read_to_string(puzzle.module_path())
+        .ok()
+        .and_then(|source| {
+            source
+                .lines()
+                .take_while(|line| line.starts_with("//!"))
+                .find_map(|line| line.strip_prefix("//! # "))
+                .map(|title| title.trim().to_string())
+        });
+
+    from_module.or_else(|| {
+        let title = Puzzle::load(puzzle)?.title?;
+        Some(format!("Day {}: {title}", puzzle.day.into_inner()))
+    })
+}
+
+/// Creates the benchmark table with a section per year. The rows of `timings` replace the rows of the same
+/// days in the section of `year`, the other rows and the sections of other years are kept from the `existing` table.
+fn construct_table(
+    prefix: &str,
+    existing: &str,
+    year: Year,
+    timings: &[Timings],
+    title: impl Fn(PuzzleId) -> Option<String>,
+) -> String {
+    let (current, mut sections): (Vec<_>, Vec<_>) = year_sections(existing)
+        .into_iter()
+        .partition(|(other, _)| *other == year);
 
+    let mut rows = current
+        .first()
+        .map(|(_, section)| existing_rows(section))
+        .unwrap_or_default();
+    rows.extend(timings.iter().map(|timing| timing_row(timing, &title)));
+
+    sections.push((year, year_section(prefix, year, &rows)));
+    // the latest year goes first.
+    sections.sort_by_key(|(year, _)| Reverse(*year));
+
+    let mut lines: Vec<String> = vec![MARKER.into(), format!("{prefix} Benchmarks")];
+    for (_, section) in sections {
+        lines.push(String::new());
+        lines.push(section);
+    }
+    lines.push(MARKER.into());
+
+    lines.join("\n")
+}
+
+/// A row of the table with the time it adds to the total of its year.
+struct Row {
+    line: String,
+    millis: f64,
+}
+
+/// The rows of a year section, keyed by the path of the solution, e.g. `./src/bin/2023-01.rs`.
+type Rows = BTreeMap<String, Row>;
+
+/// The table of one year, e.g. `### 2023` followed by a row per day and the total.
+fn year_section(prefix: &str, year: Year, rows: &Rows) -> String {
     let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
+        format!("{prefix}# {year}"),
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        "| Day | Parse | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: | :---:  |".into(),
     ];
+    lines.extend(rows.values().map(|row| row.line.clone()));
 
-    for timing in timings {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
-    }
-
+    let total_millis: f64 = rows.values().map(|row| row.millis).sum();
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+fn timing_row(timing: &Timings, title: impl Fn(PuzzleId) -> Option<String>) -> (String, Row) {
+    let path = get_path_for_bin(timing.puzzle);
+    let title =
+        title(timing.puzzle).unwrap_or_else(|| format!("Day {}", timing.puzzle.day.into_inner()));
+    let line = format!(
+        "| [{}]({}) | `{}` | `{}` | `{}` |",
+        title.replace('|', "\\|"),
+        path,
+        format_timing(timing.parse),
+        format_status(&timing.part_1),
+        format_status(&timing.part_2)
+    );
+    let millis = timing.total_nanos / 1_000_000_f64;
+
+    (path, Row { line, millis })
+}
+
+/// The rows of an existing year section. Their times are read back from the formatted cells.
+fn existing_rows(section: &str) -> Rows {
+    section
+        .lines()
+        .filter(|line| line.starts_with("| ["))
+        .filter_map(|line| {
+            let (_, link) = line.split_once("](")?;
+            let (path, cells) = link.split_once(')')?;
+            let millis = cells
+                .split('|')
+                .filter_map(|cell| parse_millis(cell.trim().trim_matches('`')))
+                .sum();
+            let row = Row {
+                line: line.to_string(),
+                millis,
+            };
+            Some((path.to_string(), row))
+        })
+        .collect()
+}
+
+/// Parses a duration as formatted by [`format_timing`], e.g. `12.3ms`, into milliseconds.
+fn parse_millis(cell: &str) -> Option<f64> {
+    [("ns", 1e-6), ("µs", 1e-3), ("ms", 1.0), ("s", 1e3)]
+        .iter()
+        .find_map(|(unit, factor)| Some(cell.strip_suffix(unit)?.parse::<f64>().ok()? * factor))
+}
+
+/// The sections of an existing table by year. Each section starts with a heading that is only the year,
+/// e.g. `### 2023`, and ends before the next heading or the closing marker.
+fn year_sections(table: &str) -> Vec<(Year, String)> {
+    let mut sections: Vec<(Year, Vec<&str>)> = vec![];
+
+    for line in table.lines() {
+        let heading = line
+            .strip_prefix('#')
+            .and_then(|heading| heading.trim_start_matches('#').trim().parse::<Year>().ok());
+
+        match (heading, sections.last_mut()) {
+            (Some(year), _) => sections.push((year, vec![line])),
+            (None, Some((_, lines))) if !line.contains(MARKER) => lines.push(line),
+            _ => {}
+        }
+    }
+
+    sections
+        .into_iter()
+        .map(|(year, lines)| (year, lines.join("\n").trim_end().to_string()))
+        .collect()
+}
+
+fn format_timing(stats: Option<Stats>) -> String {
+    stats.map_or_else(|| "-".into(), |s| format!("{:.1?}", s.median))
+}
+
+fn format_status(status: &PartStatus) -> String {
+    match status {
+        PartStatus::Panicked { .. } => "panicked".into(),
+        PartStatus::Failed { .. } => "failed".into(),
+        PartStatus::TimedOut(timeout) => format!("timed out after {}s", timeout.as_secs()),
+        status => format_timing(status.stats()),
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: &[Timings],
+    title: impl Fn(PuzzleId) -> Option<String>,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let existing = &s[positions.pos_start..positions.pos_end];
+    let table = construct_table("##", existing, year, timings, title);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
+/// Writes the benchmarks of `timings` to the section of `year` in the readme. Benchmarks of other days
+/// and years are kept, and the total of the year is summed over all its rows.
+pub fn update(year: Year, timings: &[Timings]) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, year, timings, puzzle_title)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{update_content, PartStatus, Timings, MARKER};
+    use crate::{day, template::stats::Stats, year, PuzzleId};
+    use std::time::Duration;
+
+    fn millis(ms: u64) -> Option<Stats> {
+        Some(Stats::single(Duration::from_millis(ms)))
+    }
+
+    fn solved(ms: u64) -> PartStatus {
+        PartStatus::Solved {
+            answer: "42".into(),
+            stats: Stats::single(Duration::from_millis(ms)),
+        }
+    }
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
-            Timings {
-                day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
-                total_nanos: 3e+10,
-            },
-            Timings {
-                day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
-                total_nanos: 7e+10,
-            },
-            Timings {
-                day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
-                total_nanos: 9e+10,
-            },
+            Timings::new(
+                PuzzleId::new(year!(2023), day!(1)),
+                None,
+                solved(10),
+                solved(20),
+            ),
+            Timings::new(
+                PuzzleId::new(year!(2023), day!(2)),
+                millis(5),
+                solved(30),
+                solved(40),
+            ),
+            Timings::new(
+                PuzzleId::new(year!(2023), day!(4)),
+                None,
+                solved(40),
+                solved(50),
+            ),
+            Timings::new(
+                PuzzleId::new(year!(2023), day!(5)),
+                None,
+                PartStatus::Unsolved,
+                PartStatus::Panicked {
+                    message: "not yet implemented".into(),
+                    location: None,
+                },
+            ),
+            Timings::new(
+                PuzzleId::new(year!(2023), day!(6)),
+                millis(1),
+                PartStatus::TimedOut(Duration::from_secs(60)),
+                solved(2),
+            ),
         ]
     }
 
@@ -134,28 +375,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), &get_mock_timings(), |_| None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), &get_mock_timings(), |_| None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), &get_mock_timings(), |_| None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), &get_mock_timings(), |_| None).unwrap();
+        update_content(&mut s, year!(2023), &get_mock_timings(), |_| None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -163,24 +404,91 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let title = |puzzle: PuzzleId| (puzzle.day == 2).then(|| "Day 2: Cube | Conundrum".into());
+        update_content(&mut s, year!(2023), &get_mock_timings(), title).unwrap();
         let expected = [
             "foo",
             "bar",
             "<!--- benchmarking table --->",
             "## Benchmarks",
             "",
-            "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "### 2023",
             "",
-            "**Total: 190.00ms**",
+            "| Day | Parse | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: | :---:  |",
+            "| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms` | `20.0ms` |",
+            "| [Day 2: Cube \\| Conundrum](./src/bin/2023-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `-` | `40.0ms` | `50.0ms` |",
+            "| [Day 5](./src/bin/2023-05.rs) | `-` | `-` | `panicked` |",
+            "| [Day 6](./src/bin/2023-06.rs) | `1.0ms` | `timed out after 60s` | `2.0ms` |",
+            "",
+            "**Total: 198.00ms**",
             "<!--- benchmarking table --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn keeps_benchmarks_of_other_years() {
+        let timings_2022 = vec![Timings::new(
+            PuzzleId::new(year!(2022), day!(1)),
+            None,
+            solved(1),
+            solved(2),
+        )];
+
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2023), &get_mock_timings(), |_| None).unwrap();
+        update_content(&mut s, year!(2022), &timings_2022, |_| None).unwrap();
+        let both = s.clone();
+        update_content(&mut s, year!(2023), &get_mock_timings(), |_| None).unwrap();
+        update_content(&mut s, year!(2022), &timings_2022, |_| None).unwrap();
+        assert_eq!(s, both);
+
+        assert_eq!(s.matches("### 2023").count(), 1);
+        assert_eq!(s.matches("### 2022").count(), 1);
+        assert!(s.find("### 2023") < s.find("### 2022"));
+        assert!(s.contains(
+            "### 2022\n\n| Day | Parse | Part 1 | Part 2 |\n| :---: | :---: | :---: | :---:  |\n\
+             | [Day 1](./src/bin/2022-01.rs) | `-` | `1.0ms` | `2.0ms` |\n\n**Total: 3.00ms**\n<!---"
+        ));
+        assert!(s.contains("| [Day 6](./src/bin/2023-06.rs) |"));
+        assert!(!s.contains("2022-06"));
+    }
+
+    #[test]
+    fn keeps_rows_of_other_days() {
+        let mut s = format!("foo\n{}\n{}\nbaz", MARKER, MARKER);
+        update_content(&mut s, year!(2023), &get_mock_timings(), |_| None).unwrap();
+
+        let day_3 = Timings::new(
+            PuzzleId::new(year!(2023), day!(3)),
+            None,
+            solved(1),
+            solved(2),
+        );
+        let day_4 = Timings::new(
+            PuzzleId::new(year!(2023), day!(4)),
+            millis(2),
+            solved(4),
+            solved(6),
+        );
+        update_content(&mut s, year!(2023), &[day_4, day_3], |_| None).unwrap();
+
+        let rows: Vec<&str> = s.lines().filter(|line| line.starts_with("| [")).collect();
+        assert_eq!(
+            rows,
+            [
+                "| [Day 1](./src/bin/2023-01.rs) | `-` | `10.0ms` | `20.0ms` |",
+                "| [Day 2](./src/bin/2023-02.rs) | `5.0ms` | `30.0ms` | `40.0ms` |",
+                "| [Day 3](./src/bin/2023-03.rs) | `-` | `1.0ms` | `2.0ms` |",
+                "| [Day 4](./src/bin/2023-04.rs) | `2.0ms` | `4.0ms` | `6.0ms` |",
+                "| [Day 5](./src/bin/2023-05.rs) | `-` | `-` | `panicked` |",
+                "| [Day 6](./src/bin/2023-06.rs) | `1.0ms` | `timed out after 60s` | `2.0ms` |",
+            ]
+        );
+        assert!(s.contains("**Total: 123.00ms**"));
+    }
 }
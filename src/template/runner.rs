@@ -1,63 +1,497 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
-use crate::Day;
+use crate::template::{
+    answers,
+    aoc_client::{AocClient, Verdict},
+    json,
+    ledger::{self, Submission, LEDGER_PATH},
+    output::{out, outln},
+    readme_benchmarks::{PartStatus, Timings},
+    stats::Stats,
+    Error, ANSI_ITALIC, ANSI_RESET,
+};
+use crate::PuzzleId;
+use std::cell::{Cell, RefCell};
+use std::convert::Infallible;
 use std::fmt::Display;
 use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe, PanicHookInfo};
+use std::process::ExitCode;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex, Once, PoisonError, RwLock, RwLockReadGuard};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, env, fs, thread};
 
 use super::ANSI_BOLD;
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// A solution that can be run in-process, see [`crate::solution`].
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    pub run: fn(&str) -> Timings,
+}
+
+/// Stack size of the thread that runs a part with a timeout.
+const WORKER_STACK_SIZE: usize = 64 * 1024 * 1024;
+
+/// How the runner reports results.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output for the terminal.
+    #[default]
+    Human,
+    /// One JSON record per line, see [`part_record`].
+    Json,
+}
+
+impl OutputFormat {
+    /// Reads the `--format` argument passed to the current process.
+    #[must_use]
+    pub fn from_args() -> Self {
+        let args: Vec<String> = env::args().collect();
+        args.iter()
+            .position(|x| x == "--format")
+            .and_then(|i| args.get(i + 1))
+            .and_then(|x| x.parse().ok())
+            .unwrap_or_default()
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(Self::Human),
+            "json" => Ok(Self::Json),
+            _ => Err(format!(
+                "unknown output format \"{s}\", expecting \"human\" or \"json\""
+            )),
+        }
+    }
+}
+
+impl Display for OutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Human => f.write_str("human"),
+            Self::Json => f.write_str("json"),
+        }
+    }
+}
+
+/// Runs the parse function of a solution, e.g. `solution!(2023, 5, parse)`, and reports its timing.
+/// A panic in the parse function is caught and returned as [`PartStatus::Panicked`].
+pub fn run_parse<I: Clone, P>(
+    func: impl Fn(I) -> P,
+    input: I,
+    puzzle: PuzzleId,
+) -> Result<(P, Stats), Box<PartStatus>> {
+    run_try_parse(|input| Ok::<_, Infallible>(func(input)), input, puzzle)
+}
+
+/// Like [`run_parse`] for parse functions that return a [`Result`], e.g. `solution!(2023, 5, parse?)`.
+/// An error is reported like the error of a part and returned as [`PartStatus::Failed`].
+pub fn run_try_parse<I: Clone, P, E: Display>(
+    func: impl Fn(I) -> Result<P, E>,
+    input: I,
+    puzzle: PuzzleId,
+) -> Result<(P, Stats), Box<PartStatus>> {
+    let format = OutputFormat::from_args();
+
+    let status = match catch_panic(|| run_timed(func, input, |_| print_parse_start(format))) {
+        Ok((Ok(parsed), stats)) => {
+            print_parsed(&stats, puzzle, format);
+            return Ok((parsed, stats));
+        }
+        Ok((Err(e), _)) => Box::new(PartStatus::Failed {
+            message: e.to_string(),
+        }),
+        Err(status) => status,
+    };
+
+    print_failure(&status, puzzle, None, format);
+    Err(status)
+}
+
+fn print_parse_start(format: OutputFormat) {
+    if format == OutputFormat::Human {
+        out!("Parse:");
+    }
+}
+
+fn print_parsed(stats: &Stats, puzzle: PuzzleId, format: OutputFormat) {
+    match format {
+        OutputFormat::Human => {
+            out!("\r");
+            outln!("Parse:{}", format_duration(stats));
+        }
+        OutputFormat::Json => {
+            let record = parse_record(puzzle, "parsed").object("stats", &stats_record(stats));
+            outln!("{record}");
+        }
+    }
+}
+
+/// The return type of a part: [`Option`] for parts that are not solved yet, or [`Result`] for parts
+/// that report why they could not solve the input, e.g. with a [`crate::parse::InputError`].
+pub trait PartResult {
+    type Answer: Display;
+
+    /// The answer of the part, [`None`] if it is not solved yet, or the error it returned.
+    fn into_answer(self) -> Result<Option<Self::Answer>, String>;
+}
+
+impl<T: Display> PartResult for Option<T> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        Ok(self)
+    }
+}
+
+impl<T: Display, E: Display> PartResult for Result<T, E> {
+    type Answer = T;
+
+    fn into_answer(self) -> Result<Option<T>, String> {
+        self.map(Some).map_err(|e| e.to_string())
+    }
+}
+
+/// Runs one part of a solution and reports its result.
+/// A panic in the part is caught and reported as [`PartStatus::Panicked`], so the next part still runs.
+/// An error returned by the part is reported as [`PartStatus::Failed`].
+pub fn run_part<F, I, R>(func: F, input: I, puzzle: PuzzleId, part: u8) -> PartStatus
+where
+    F: Fn(I) -> R + Copy,
+    I: Clone,
+    R: PartResult,
+{
+    report_part(func, input, puzzle, part, None, run_once)
+}
+
+/// Like [`run_part`], but if the part does not finish within `timeout`, it is reported as
+/// [`PartStatus::TimedOut`] and left running in the background, see [`part_timeout`].
+pub fn run_part_with_timeout<F, I, R>(
+    func: F,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    timeout: Duration,
+) -> PartStatus
+where
+    F: Fn(I) -> R + Copy + Send + 'static,
+    I: Clone + Send + 'static,
+    R: PartResult + Send + 'static,
+{
+    report_part(func, input, puzzle, part, Some(timeout), |func, input| {
+        run_with_timeout(func, input, timeout)
+    })
+}
+
+/// Reports the result of `first_run` and benchmarks the part if `--time` was passed, see [`should_bench`].
+fn report_part<F, I, R>(
+    func: F,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+    timeout: Option<Duration>,
+    first_run: impl FnOnce(F, I) -> Result<(R, Duration), Box<PartStatus>>,
+) -> PartStatus
+where
+    F: Fn(I) -> R + Copy,
+    I: Clone,
+    R: PartResult,
+{
+    let format = OutputFormat::from_args();
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let first_run = {
+        let _shared = shared_run();
+        first_run(func, input.clone())
+    };
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let (result, base_time) = match first_run {
+        Ok((result, base_time)) => match result.into_answer() {
+            Ok(result) => (result, base_time),
+            Err(message) => {
+                let status = PartStatus::Failed { message };
+                print_failure(&status, puzzle, Some(part), format);
+                return status;
+            }
+        },
+        Err(status) => {
+            print_failure(&status, puzzle, Some(part), format);
+            return *status;
+        }
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if format == OutputFormat::Human {
+        print_result(&result, &part_str, "");
+    }
+
+    let stats = if env::args().any(|x| x == "--time") && should_bench(base_time, timeout) {
+        bench(func, input, &base_time)
+    } else {
+        Stats::single(base_time)
+    };
+
+    match format {
+        OutputFormat::Human => print_result(&result, &part_str, &format_duration(&stats)),
+        OutputFormat::Json => {
+            let record = match &result {
+                Some(result) => {
+                    part_record(puzzle, part, "solved").string("answer", &result.to_string())
+                }
+                None => part_record(puzzle, part, "unsolved").null("answer"),
+            }
+            .object("stats", &stats_record(&stats));
+            outln!("{record}");
+        }
+    }
+
+    match result {
+        Some(result) => PartStatus::Solved {
+            answer: result.to_string(),
+            stats,
+        },
+        None => PartStatus::Unsolved,
     }
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
+/// Resolves the timeout of a part. The timeout of the day, e.g. `solution!(2023, 5, timeout = 300)`,
+/// takes precedence over the default in seconds from the `AOC_TIMEOUT` environment variable.
+/// A timeout of `0` disables the timeout.
+///
+/// Parts whose first run took more than a tenth of their timeout are not benchmarked, see [`should_bench`].
+///
+/// A part that timed out cannot be stopped and keeps running on its thread until the process exits,
+/// competing for the CPU with everything that runs after it. `all` reports the timings of days that ran
+/// meanwhile as unreliable and does not store them, see [`running_timed_out_parts`].
+#[must_use]
+pub fn part_timeout(day_timeout: Option<u64>) -> Option<Duration> {
+    let seconds = day_timeout.or_else(|| env::var("AOC_TIMEOUT").ok()?.parse().ok())?;
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
+
+/// Runs `func` once and returns the result and the time it took.
+fn run_once<F, I, T>(func: F, input: I) -> Result<(T, Duration), Box<PartStatus>>
+where
+    F: FnOnce(I) -> T,
+{
+    catch_panic(|| {
+        let timer = Instant::now();
+        let result = func(input);
+        (result, timer.elapsed())
+    })
+}
+
+/// Whether each part that timed out has finished, see [`running_timed_out_parts`].
+static TIMED_OUT: Mutex<Vec<Arc<AtomicBool>>> = Mutex::new(vec![]);
+
+/// The number of parts that timed out and are still running in the background.
+/// Timings taken while they run are unreliable, see [`part_timeout`].
+pub fn running_timed_out_parts() -> usize {
+    let mut timed_out = TIMED_OUT.lock().unwrap_or_else(PoisonError::into_inner);
+    timed_out.retain(|finished| !finished.load(Ordering::Acquire));
+    timed_out.len()
+}
+
+/// Like [`run_once`], but runs `func` on a separate thread and waits at most `timeout` for it to finish.
+fn run_with_timeout<F, I, T>(
+    func: F,
     input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
-    let timer = Instant::now();
-    let result = func(input.clone());
-    let base_time = timer.elapsed();
+    timeout: Duration,
+) -> Result<(T, Duration), Box<PartStatus>>
+where
+    F: FnOnce(I) -> T + Send + 'static,
+    I: Send + 'static,
+    T: Send + 'static,
+{
+    let (sender, receiver) = mpsc::channel();
+    let finished = Arc::new(AtomicBool::new(false));
+    let worker_finished = Arc::clone(&finished);
+
+    // the main thread usually has a larger stack than spawned threads, keep recursive solutions working.
+    let spawned = thread::Builder::new()
+        .stack_size(WORKER_STACK_SIZE)
+        .spawn(move || {
+            // the receiver is gone if the part timed out.
+            let _ = sender.send(run_once(func, input));
+            worker_finished.store(true, Ordering::Release);
+        });
+
+    if let Err(e) = spawned {
+        return Err(Box::new(PartStatus::Panicked {
+            message: format!("could not spawn a thread for the timeout: {e}"),
+            location: None,
+        }));
+    }
+
+    receiver.recv_timeout(timeout).unwrap_or_else(|_| {
+        TIMED_OUT
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(finished);
+        Err(Box::new(PartStatus::TimedOut(timeout)))
+    })
+}
+
+thread_local! {
+    /// Whether panics on this thread are caught by [`catch_panic`].
+    static IS_CATCHING: Cell<bool> = const { Cell::new(false) };
+    /// The last panic caught by [`catch_panic`] on this thread.
+    static CAUGHT_PANIC: RefCell<Option<PartStatus>> = const { RefCell::new(None) };
+}
+
+/// Runs `func` and turns a panic into [`PartStatus::Panicked`].
+///
+/// Panics outside of this function are still reported by the default panic hook.
+fn catch_panic<T>(func: impl FnOnce() -> T) -> Result<T, Box<PartStatus>> {
+    static INSTALL_HOOK: Once = Once::new();
+
+    INSTALL_HOOK.call_once(|| {
+        let default_hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if IS_CATCHING.get() {
+                CAUGHT_PANIC.set(Some(panicked_status(info)));
+            } else {
+                default_hook(info);
+            }
+        }));
+    });
+
+    let was_catching = IS_CATCHING.replace(true);
+    let result = panic::catch_unwind(AssertUnwindSafe(func));
+    IS_CATCHING.set(was_catching);
+
+    result.map_err(|_| {
+        Box::new(CAUGHT_PANIC.take().unwrap_or(PartStatus::Panicked {
+            message: "unknown panic".into(),
+            location: None,
+        }))
+    })
+}
+
+fn panicked_status(info: &PanicHookInfo) -> PartStatus {
+    let payload = info.payload();
+    let message = payload
+        .downcast_ref::<&str>()
+        .map(|s| (*s).to_string())
+        .or_else(|| payload.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "Box<dyn Any>".into());
+
+    PartStatus::Panicked {
+        message,
+        location: info.location().map(ToString::to_string),
+    }
+}
+
+/// Prints a part, or the parse step if `part` is [`None`], that did not return a result.
+fn print_failure(status: &PartStatus, puzzle: PuzzleId, part: Option<u8>, format: OutputFormat) {
+    let (label, record) = match part {
+        Some(part) => (
+            format!("Part {part}"),
+            part_record(puzzle, part, status.name()),
+        ),
+        None => ("Parse".to_string(), parse_record(puzzle, status.name())),
+    };
+
+    match (status, format) {
+        (PartStatus::TimedOut(timeout), OutputFormat::Human) => {
+            out!("\r");
+            outln!("{label}: ✖ timed out after {}s", timeout.as_secs());
+        }
+        (PartStatus::TimedOut(timeout), OutputFormat::Json) => {
+            outln!("{}", record.number("timeout_s", timeout.as_secs()));
+        }
+        (PartStatus::Panicked { message, location }, OutputFormat::Human) => {
+            let location = location
+                .as_ref()
+                .map(|l| format!(" at {l}"))
+                .unwrap_or_default();
+            out!("\r");
+            outln!("{label}: ✖ panicked{location}: {message}");
+        }
+        (PartStatus::Failed { message }, OutputFormat::Human) => {
+            out!("\r");
+            outln!(
+                "{label}: ✖ {puzzle} {} failed: {message}",
+                label.to_lowercase()
+            );
+        }
+        (PartStatus::Failed { message }, OutputFormat::Json) => {
+            outln!("{}", record.string("message", message));
+        }
+        (PartStatus::Panicked { message, location }, OutputFormat::Json) => {
+            let record = record.string("message", message);
+            let record = match location {
+                Some(location) => record.string("location", location),
+                None => record.null("location"),
+            };
+            outln!("{record}");
+        }
+        (PartStatus::Solved { .. } | PartStatus::Unsolved, _) => {}
+    }
+}
+
+/// Run a solution part or parse function. The behavior differs depending on whether we are running a release or debug build:
+///  1. in debug, the function is executed once.
+///  2. in release, the function is warmed up and then benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+fn run_timed<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, Stats) {
+    let (result, base_time) = {
+        let _shared = shared_run();
+        let timer = Instant::now();
+        (func(input.clone()), timer.elapsed())
+    };
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let stats = if std::env::args().any(|x| x == "--time") {
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        Stats::single(base_time)
     };
 
-    (result, run.0, run.1)
+    (result, stats)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Benchmarks take at least 10 more runs, so parts whose first run took more than this fraction of their
+/// timeout are not benchmarked. Otherwise a part just under the timeout would hold [`BENCH_LOCK`] for
+/// about ten times its timeout.
+const BENCH_TIMEOUT_DIVISOR: u32 = 10;
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Whether a part whose first run took `base_time` is benchmarked, see [`BENCH_TIMEOUT_DIVISOR`].
+/// Parts without a timeout are always benchmarked.
+fn should_bench(base_time: Duration, timeout: Option<Duration>) -> bool {
+    timeout.is_none_or(|timeout| base_time <= timeout / BENCH_TIMEOUT_DIVISOR)
+}
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+/// Held while a solution function runs outside of a benchmark. Benchmarks take the lock exclusively,
+/// so solutions that run in parallel (see `all --jobs`) do not compete with them for the CPU.
+static BENCH_LOCK: RwLock<()> = RwLock::new(());
+
+fn shared_run() -> RwLockReadGuard<'static, ()> {
+    BENCH_LOCK.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> Stats {
+    let _exclusive = BENCH_LOCK.write().unwrap_or_else(PoisonError::into_inner);
+
+    if OutputFormat::from_args() == OutputFormat::Human {
+        out!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
+    }
+
+    let base_nanos = cmp::max(base_time.as_nanos(), 10);
+
+    // warm up caches and branch predictors before taking measurements.
+    let warmup_iterations = (Duration::from_millis(100).as_nanos() / base_nanos).clamp(1, 1000);
+
+    for _ in 0..warmup_iterations {
+        func(input.clone());
+    }
+
+    let bench_iterations = (Duration::from_secs(1).as_nanos() / base_nanos).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -69,29 +503,59 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    Stats::from_samples(&timers).unwrap_or_else(|| Stats::single(*base_time))
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
-    if samples == 1 {
-        format!(" ({duration:.1?})")
+fn format_duration(stats: &Stats) -> String {
+    if stats.samples == 1 {
+        format!(" ({:.1?})", stats.median)
     } else {
-        format!(" ({duration:.1?} @ {samples} samples)")
+        format!(
+            " (median {:.1?} ±{:.1?}, min {:.1?}, p95 {:.1?}, max {:.1?} @ {} samples)",
+            stats.median, stats.stddev, stats.min, stats.p95, stats.max, stats.samples
+        )
     }
 }
 
+/// Creates the JSON record that is printed for a part in [`OutputFormat::Json`].
+/// Records contain `year`, `day`, `step` (`"part"`), the number of the `part` and `status` and, depending
+/// on the status, `answer` and `stats` or `message` and `location` for panics and `timeout_s` for timeouts.
+#[must_use]
+pub fn part_record(puzzle: PuzzleId, part: u8, status: &str) -> json::Object {
+    puzzle_record(puzzle)
+        .string("step", "part")
+        .number("part", part)
+        .string("status", status)
+}
+
+/// Creates the JSON record that is printed for the parse step in [`OutputFormat::Json`], see [`part_record`].
+/// Parse records have no `part`, their `step` is `"parse"`.
+#[must_use]
+pub fn parse_record(puzzle: PuzzleId, status: &str) -> json::Object {
+    puzzle_record(puzzle)
+        .string("step", "parse")
+        .string("status", status)
+}
+
+/// Creates a JSON record that identifies `puzzle`.
+#[must_use]
+pub fn puzzle_record(puzzle: PuzzleId) -> json::Object {
+    json::Object::new()
+        .number("year", puzzle.year.into_inner())
+        .number("day", puzzle.day.into_inner())
+}
+
+fn stats_record(stats: &Stats) -> json::Object {
+    json::Object::new()
+        .number("samples", stats.samples)
+        .number("median_ns", stats.median.as_nanos())
+        .number("mean_ns", stats.mean.as_nanos())
+        .number("stddev_ns", stats.stddev.as_nanos())
+        .number("min_ns", stats.min.as_nanos())
+        .number("p95_ns", stats.p95.as_nanos())
+        .number("max_ns", stats.max.as_nanos())
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -100,68 +564,245 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    out!("\r");
+                    outln!("{str}");
+                    outln!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    out!("\r");
+                    outln!("{str}");
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                out!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                out!("\r");
+                outln!("{part}: ✖             ");
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+/// Runs `solution` on its input and submits a part if `--submit <part>` was passed, see [`submit_result`].
+/// This is the `main` of every solution binary, see [`crate::solution`].
+///
+/// Fails with [`Error::NotSolved`] if a part returned no answer and with [`Error::Failed`] if a part
+/// panicked or timed out, so that the exit code tells how the solution did.
+pub fn run_main(solution: &Solution) -> ExitCode {
+    match run_binary(solution) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            if !e.is_reported() {
+                eprintln!("Error: {e}");
+            }
+            e.exit_code()
+        }
+    }
+}
+
+fn run_binary(solution: &Solution) -> Result<(), Error> {
+    let puzzle = solution.puzzle;
+    let input_path = puzzle.input_path();
+    let input = fs::read_to_string(&input_path).map_err(|e| Error::file(input_path, e))?;
+
+    let timings = (solution.run)(&input);
+    let statuses = [&timings.part_1, &timings.part_2];
+
+    if let Some(part) = submit_arg()? {
+        if let PartStatus::Solved { answer, .. } = statuses[usize::from(part - 1)] {
+            submit_result(answer, puzzle, part)?;
+        }
+    }
+
+    if statuses.iter().any(|s| {
+        matches!(
+            s,
+            PartStatus::Panicked { .. } | PartStatus::Failed { .. } | PartStatus::TimedOut(_)
+        )
+    }) {
+        return Err(Error::Failed(format!(
+            "a part of {puzzle} panicked, failed or timed out."
+        )));
+    }
+
+    let unsolved = (1..)
+        .zip(statuses)
+        .find(|(_, s)| **s == PartStatus::Unsolved);
+    match unsolved {
+        Some((part, _)) => Err(Error::NotSolved(format!(
+            "part {part} of {puzzle} is not solved."
+        ))),
+        None => Ok(()),
+    }
+}
+
+/// Reads the part passed with `--submit <part>` to the current process.
+fn submit_arg() -> Result<Option<u8>, Error> {
     let args: Vec<String> = env::args().collect();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+    let Some(index) = args.iter().position(|x| x == "--submit") else {
+        return Ok(None);
+    };
+
+    match args.get(index + 1).and_then(|x| x.parse().ok()) {
+        Some(part @ 1..=2) => Ok(Some(part)),
+        _ => Err(Error::Usage(
+            "unexpected command-line input. Format: cargo solve 1 --submit 1".into(),
+        )),
+    }
+}
+
+/// Submits `answer` as the answer of one part of `puzzle` unless the submission ledger shows the part
+/// as solved or the answer as wrong. Returns the verdict if the answer was submitted.
+///
+/// Every submission is recorded in the ledger together with its verdict. Accepted answers are also
+/// stored as the known answer of the part, see [`answers`].
+fn submit_result(answer: &str, puzzle: PuzzleId, part: u8) -> Result<Option<Verdict>, Error> {
+    let client = AocClient::from_env()?;
+    let submissions = ledger::load(LEDGER_PATH)?;
+
+    let format = OutputFormat::from_args();
+    let record = puzzle_record(puzzle)
+        .string("step", "submit")
+        .number("part", part);
+
+    if let Err(rejection) = ledger::check(&submissions, puzzle, part, answer) {
+        match format {
+            OutputFormat::Human => outln!("Not submitting result. {rejection}"),
+            OutputFormat::Json => outln!(
+                "{}",
+                record
+                    .string("status", "skipped")
+                    .string("message", &rejection.to_string())
+            ),
+        }
+        return Ok(None);
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    if format == OutputFormat::Human {
+        outln!("Submitting result...");
+    }
+    let verdict = client.submit(puzzle, part, answer)?;
+    match format {
+        OutputFormat::Human => outln!("{verdict}"),
+        OutputFormat::Json => outln!(
+            "{}",
+            record
+                .string("status", "submitted")
+                .string("verdict", verdict.name())
+                .string("message", &verdict.to_string())
+        ),
     }
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    ledger::append(
+        LEDGER_PATH,
+        &Submission::new(puzzle, part, answer, verdict.clone()),
+    )?;
+
+    if verdict == Verdict::Correct {
+        answers::store(puzzle, part, answer)
+            .map_err(|e| Error::file(puzzle.answer_path(part), e))?;
+    }
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    Ok(Some(verdict))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        catch_panic, parse_record, part_record, part_timeout, run_parse, run_try_parse,
+        run_with_timeout, running_timed_out_parts, should_bench, PartStatus,
     };
+    use crate::{day, year, PuzzleId};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn catches_panics() {
+        assert_eq!(catch_panic(|| 42), Ok(42));
+
+        let Err(status) = catch_panic(|| -> u32 { panic!("part {} failed", 2) }) else {
+            panic!("expected a panic");
+        };
+        let PartStatus::Panicked { message, location } = *status else {
+            panic!("expected a panic");
+        };
+        assert_eq!(message, "part 2 failed");
+        assert!(location.is_some_and(|l| l.starts_with("src/template/runner.rs:")));
+    }
 
-    if part_submit != part {
-        return None;
+    #[test]
+    fn catches_parse_panics_and_errors() {
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+
+        let (parsed, _) = run_parse(|x: u32| x * 2, 21, puzzle).unwrap();
+        assert_eq!(parsed, 42);
+
+        let status = run_parse(|_: u32| -> u32 { panic!("bad input") }, 0, puzzle).unwrap_err();
+        assert!(
+            matches!(*status, PartStatus::Panicked { ref message, .. } if message == "bad input")
+        );
+
+        let status = run_try_parse(|_: u32| Err::<u32, _>("no seeds"), 0, puzzle).unwrap_err();
+        assert_eq!(
+            *status,
+            PartStatus::Failed {
+                message: "no seeds".into()
+            }
+        );
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn times_out_parts() {
+        let timeout = Duration::from_millis(50);
+
+        let (result, _) = run_with_timeout(|x: u32| x * 2, 21, timeout).unwrap();
+        assert_eq!(result, 42);
+        assert_eq!(running_timed_out_parts(), 0);
+
+        let slow = |x: u32| {
+            thread::sleep(Duration::from_secs(5));
+            x
+        };
+        assert_eq!(
+            run_with_timeout(slow, 0, timeout),
+            Err(Box::new(PartStatus::TimedOut(Duration::from_millis(50))))
+        );
+        assert_eq!(running_timed_out_parts(), 1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn json_records_have_a_step() {
+        let puzzle = PuzzleId::new(year!(2023), day!(5));
+        assert_eq!(
+            part_record(puzzle, 1, "unsolved").to_string(),
+            r#"{"year":2023,"day":5,"step":"part","part":1,"status":"unsolved"}"#
+        );
+        assert_eq!(
+            parse_record(puzzle, "parsed").to_string(),
+            r#"{"year":2023,"day":5,"step":"parse","status":"parsed"}"#
+        );
+    }
+
+    #[test]
+    fn skips_benchmarks_of_parts_close_to_their_timeout() {
+        let timeout = Some(Duration::from_secs(60));
+        assert!(should_bench(Duration::from_secs(59), None));
+        assert!(should_bench(Duration::from_secs(6), timeout));
+        assert!(!should_bench(Duration::from_millis(6001), timeout));
+        assert!(!should_bench(Duration::from_secs(59), timeout));
+    }
+
+    #[test]
+    fn day_timeouts_override_the_default() {
+        assert_eq!(part_timeout(Some(300)), Some(Duration::from_secs(300)));
+        assert_eq!(part_timeout(Some(0)), None);
+    }
 }
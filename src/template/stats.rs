@@ -0,0 +1,139 @@
+/// Summary statistics for benchmark samples.
+use std::time::Duration;
+
+/// Summary of a set of timing samples after outlier rejection.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+}
+
+impl Stats {
+    /// Creates [`Stats`] for a single measurement.
+    #[must_use]
+    pub fn single(duration: Duration) -> Self {
+        Self {
+            samples: 1,
+            min: duration,
+            median: duration,
+            p95: duration,
+            max: duration,
+            mean: duration,
+            stddev: Duration::ZERO,
+        }
+    }
+
+    /// Computes [`Stats`] for the given samples, discarding outliers first.
+    /// Returns [`None`] if no samples were provided.
+    #[must_use]
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        let mut nanos: Vec<u128> = samples.iter().map(Duration::as_nanos).collect();
+        nanos.sort_unstable();
+        let nanos = reject_outliers(&nanos);
+
+        let (min, max) = (*nanos.first()?, *nanos.last()?);
+
+        let len = nanos.len() as f64;
+        let mean = nanos.iter().sum::<u128>() as f64 / len;
+        let variance = nanos
+            .iter()
+            .map(|&x| (x as f64 - mean).powi(2))
+            .sum::<f64>()
+            / len;
+
+        Some(Self {
+            samples: nanos.len(),
+            min: to_duration(min as f64),
+            median: to_duration(percentile(nanos, 50.0)),
+            p95: to_duration(percentile(nanos, 95.0)),
+            max: to_duration(max as f64),
+            mean: to_duration(mean),
+            stddev: to_duration(variance.sqrt()),
+        })
+    }
+}
+
+/// Drops samples outside of the Tukey fences (1.5 times the interquartile range).
+/// Expects `sorted` to be sorted in ascending order.
+fn reject_outliers(sorted: &[u128]) -> &[u128] {
+    if sorted.len() < 4 {
+        return sorted;
+    }
+
+    let q1 = percentile(sorted, 25.0);
+    let q3 = percentile(sorted, 75.0);
+    let fence = 1.5 * (q3 - q1);
+
+    let lower = q1 - fence;
+    let upper = q3 + fence;
+
+    let start = sorted.partition_point(|&x| (x as f64) < lower);
+    let end = sorted.partition_point(|&x| (x as f64) <= upper);
+
+    &sorted[start..end]
+}
+
+/// Linear interpolation between closest ranks. Expects `sorted` to be sorted and non-empty.
+fn percentile(sorted: &[u128], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let (low, high) = (rank.floor() as usize, rank.ceil() as usize);
+    let weight = rank - rank.floor();
+    sorted[low] as f64 * (1.0 - weight) + sorted[high] as f64 * weight
+}
+
+fn to_duration(nanos: f64) -> Duration {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Stats;
+    use std::time::Duration;
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|&x| Duration::from_nanos(x)).collect()
+    }
+
+    #[test]
+    fn empty_samples() {
+        assert_eq!(Stats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42])).unwrap();
+        assert_eq!(stats, Stats::single(Duration::from_nanos(42)));
+    }
+
+    #[test]
+    fn basic_stats() {
+        let stats = Stats::from_samples(&nanos(&[50, 10, 40, 20, 30])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.p95, Duration::from_nanos(48));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.stddev, Duration::from_nanos(14));
+    }
+
+    #[test]
+    fn even_median() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40])).unwrap();
+        assert_eq!(stats.median, Duration::from_nanos(25));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[10, 11, 12, 10, 11, 12, 10, 5000])).unwrap();
+        assert_eq!(stats.samples, 7);
+        assert_eq!(stats.max, Duration::from_nanos(12));
+    }
+}